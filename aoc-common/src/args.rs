@@ -0,0 +1,125 @@
+use crate::{input_name, or_exit, parse_inputfile, Lines, Normalize, STDIN};
+use std::env;
+use std::fs::File;
+use std::io::{self, IsTerminal, Read};
+
+/// Label the output of input i of count with its name, when there is more than one.
+fn print_label(i: usize, count: usize, name: &str) {
+    if count > 1 {
+        if i > 0 {
+            println!();
+        }
+        println!("==> {} <==", name);
+    }
+}
+
+/// Command line of a day binary: options of the day, then the input files.
+pub struct Args {
+    args: Vec<String>,
+}
+
+impl Args {
+    pub fn new(args: Vec<String>) -> Args {
+        Args { args }
+    }
+
+    /// Arguments the program was started with.
+    pub fn from_env() -> Args {
+        Args::new(env::args().skip(1).collect())
+    }
+
+    /// Take `--name` from the arguments, returning whether it was given.
+    pub fn flag(&mut self, name: &str) -> bool {
+        match self.args.iter().position(|arg| arg == name) {
+            Some(i) => {
+                self.args.remove(i);
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Take `--name VALUE` from the arguments, returning VALUE if it was given.
+    pub fn value(&mut self, name: &str) -> Result<Option<String>, String> {
+        let i = match self.args.iter().position(|arg| arg == name) {
+            Some(i) => i,
+            None => return Ok(None),
+        };
+        if i + 1 >= self.args.len() {
+            return Err(format!("Missing value for {}", name));
+        }
+
+        let value = self.args.remove(i + 1);
+        self.args.remove(i);
+        Ok(Some(value))
+    }
+
+    /// The remaining arguments as input files.
+    /// Without any, standard input is used, but only if something is piped into it.
+    pub fn filenames(self) -> Result<Vec<String>, String> {
+        if let Some(option) = self.args.iter().find(|arg| arg.starts_with("--")) {
+            return Err(format!("Unknown option {}", option));
+        }
+
+        if !self.args.is_empty() {
+            return Ok(self.args);
+        }
+        if io::stdin().is_terminal() {
+            return Err("No input given: pass input files, or '-' to read standard input".to_string());
+        }
+        Ok(vec![STDIN.to_string()])
+    }
+
+    /// Read every input file and run f on it, exiting on errors.
+    /// When there is more than one input, the output of each is labelled with its name.
+    pub fn for_each_input(self, normalize: Normalize, mut f: impl FnMut(&Lines)) {
+        let filenames: Vec<String> = or_exit(self.filenames());
+
+        for (i, filename) in filenames.iter().enumerate() {
+            let input: Lines = or_exit(parse_inputfile(filename, normalize));
+            print_label(i, filenames.len(), input.name());
+            f(&input);
+        }
+    }
+
+    /// Open every input file and run f on its name and contents, for days that read
+    /// their input as it comes instead of all at once. Labelled like `for_each_input`.
+    pub fn for_each_file(self, mut f: impl FnMut(&str, &mut dyn Read)) {
+        let filenames: Vec<String> = or_exit(self.filenames());
+
+        for (i, filename) in filenames.iter().enumerate() {
+            let name = input_name(filename);
+            let mut reader: Box<dyn Read> = if filename == STDIN {
+                Box::new(io::stdin().lock())
+            } else {
+                Box::new(or_exit(File::open(filename).map_err(|err| format!("Cannot read {}: {}", name, err))))
+            };
+            print_label(i, filenames.len(), name);
+            f(name, &mut reader);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn args(args: &[&str]) -> Args {
+        Args::new(args.iter().map(|arg| arg.to_string()).collect())
+    }
+
+    #[test]
+    fn options_are_taken_out() {
+        let mut args = args(&["a.txt", "--explain", "--vocabulary", "german", "b.txt"]);
+        assert!(args.flag("--explain"));
+        assert!(!args.flag("--explain"));
+        assert_eq!(args.value("--vocabulary").unwrap(), Some("german".to_string()));
+        assert_eq!(args.filenames().unwrap(), vec!["a.txt", "b.txt"]);
+    }
+
+    #[test]
+    fn unknown_option_is_an_error() {
+        assert!(args(&["--bogus", "a.txt"]).filenames().is_err());
+        assert!(args(&["a.txt", "--vocabulary"]).value("--vocabulary").is_err());
+    }
+}
@@ -0,0 +1,67 @@
+use std::error::Error;
+use std::fmt;
+
+/// Error in the puzzle input, pointing at the offending text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    pub file: String,
+    /// Line number in the file, starting from 1.
+    pub line: usize,
+    /// Column of the offending text in characters, starting from 1.
+    pub column: usize,
+    /// The offending text. Empty if something is missing.
+    pub text: String,
+    /// The whole line as it is in the file.
+    pub source_line: String,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    /// Formatted like a compiler diagnostic:
+    ///
+    /// ```text
+    /// error: invalid card 'X'
+    ///  --> input.txt:3:2
+    ///   |
+    /// 3 | 3XT3K 765
+    ///   |  ^
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let number = self.line.to_string();
+        let gutter = " ".repeat(number.len());
+        let underline = "^".repeat(self.text.chars().count().max(1));
+
+        writeln!(f, "error: {}", self.message)?;
+        writeln!(f, "{}--> {}:{}:{}", gutter, self.file, self.line, self.column)?;
+        writeln!(f, "{} |", gutter)?;
+        writeln!(f, "{} | {}", number, self.source_line)?;
+        write!(
+            f,
+            "{} | {}{}",
+            gutter,
+            " ".repeat(self.column.saturating_sub(1)),
+            underline
+        )
+    }
+}
+
+impl Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn display_underlines_text() {
+        let err = ParseError {
+            file: "input.txt".to_string(),
+            line: 3,
+            column: 2,
+            text: "X".to_string(),
+            source_line: "3XT3K 765".to_string(),
+            message: "invalid card 'X'".to_string(),
+        };
+        let expected = "error: invalid card 'X'\n --> input.txt:3:2\n  |\n3 | 3XT3K 765\n  |  ^";
+        assert_eq!(err.to_string(), expected);
+    }
+}
@@ -0,0 +1,106 @@
+//! Helpers shared by every day of Advent of Code 2023.
+
+mod args;
+mod error;
+mod lines;
+mod solution;
+mod table;
+mod timing;
+
+pub use args::Args;
+pub use error::ParseError;
+pub use lines::Lines;
+pub use solution::{Answer, Solution, Solver};
+pub use table::{csv_field, print_table};
+pub use timing::{measure, time_day, DayTimings, Iterations, Timings};
+
+use std::fmt::Display;
+use std::fs::read_to_string;
+use std::io::{self, Read};
+use std::process::exit;
+
+/// File name that stands for standard input.
+pub const STDIN: &str = "-";
+
+/// How the lines of an input file are cleaned up before a day sees them.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Normalize {
+    /// Keep blank lines instead of dropping them.
+    pub keep_empty: bool,
+    /// Strip leading and trailing whitespace from every line.
+    pub trim: bool,
+}
+
+impl Normalize {
+    /// Lines exactly as they are in the file.
+    pub const RAW: Normalize = Normalize {
+        keep_empty: true,
+        trim: false,
+    };
+
+    /// Trimmed lines with blank lines dropped.
+    pub const TRIMMED: Normalize = Normalize {
+        keep_empty: false,
+        trim: true,
+    };
+
+    /// Apply the normalization to one line.
+    /// Returns None if the line should be dropped.
+    pub fn apply(&self, line: &str) -> Option<String> {
+        let line = if self.trim { line.trim() } else { line };
+        if line.is_empty() && !self.keep_empty {
+            return None;
+        }
+
+        Some(line.to_string())
+    }
+}
+
+/// Read file filename into normalized lines. "-" reads standard input.
+pub fn parse_inputfile(filename: &str, normalize: Normalize) -> Result<Lines, String> {
+    let res = if filename == STDIN {
+        let mut text = String::new();
+        io::stdin().read_to_string(&mut text).map(|_| text)
+    } else {
+        read_to_string(filename)
+    };
+
+    match res {
+        Ok(text) => Ok(Lines::new(input_name(filename), &text, normalize)),
+        Err(err) => Err(format!("Cannot read {}: {}", input_name(filename), err)),
+    }
+}
+
+/// Name of the input in messages.
+pub fn input_name(filename: &str) -> &str {
+    if filename == STDIN {
+        "<stdin>"
+    } else {
+        filename
+    }
+}
+
+/// Read every input given on the command line and run f on it, exiting on errors.
+/// When there is more than one input, the output of each is labelled with its name.
+pub fn for_each_input(normalize: Normalize, f: impl FnMut(&Lines)) {
+    Args::from_env().for_each_input(normalize, f);
+}
+
+/// Unwrap the result, or print the error and exit with a non-zero code.
+pub fn or_exit<T, E: Display>(res: Result<T, E>) -> T {
+    match res {
+        Ok(val) => val,
+        Err(err) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+    }
+}
+
+/// Parse an example input embedded in a test, panicking with the diagnostic if it is malformed.
+pub fn parse_example<S: Solution>(text: &str) -> S::Input {
+    match S::parse(&Lines::new("example", text, S::NORMALIZE)) {
+        Ok(input) => input,
+        Err(err) => panic!("\n{}", err),
+    }
+}
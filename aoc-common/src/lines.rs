@@ -0,0 +1,160 @@
+use crate::{Normalize, ParseError};
+use std::fmt::Display;
+use std::ops::Deref;
+use std::str::FromStr;
+
+/// Normalized lines of an input file.
+/// Every line remembers where it came from, so parsers can point errors at the file.
+pub struct Lines {
+    name: String,
+    lines: Vec<String>,
+    /// Index of each line in `source`.
+    numbers: Vec<usize>,
+    /// Bytes trimmed from the start of each line.
+    offsets: Vec<usize>,
+    /// Lines exactly as they are in the file.
+    source: Vec<String>,
+}
+
+impl Lines {
+    pub fn new(name: &str, text: &str, normalize: Normalize) -> Lines {
+        let mut res = Lines {
+            name: name.to_string(),
+            lines: Vec::new(),
+            numbers: Vec::new(),
+            offsets: Vec::new(),
+            source: Vec::new(),
+        };
+
+        for (i, line) in text.lines().enumerate() {
+            if let Some(normalized) = normalize.apply(line) {
+                let offset = if normalize.trim {
+                    line.len() - line.trim_start().len()
+                } else {
+                    0
+                };
+                res.lines.push(normalized);
+                res.numbers.push(i);
+                res.offsets.push(offset);
+            }
+            res.source.push(line.to_string());
+        }
+        res
+    }
+
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Line number of line `index` in the file, starting from 1.
+    pub fn number(&self, index: usize) -> usize {
+        self.numbers[index] + 1
+    }
+
+    /// Error pointing at `text` on line `index`.
+    /// `text` should be a slice of that line, otherwise the error points at the start of the line.
+    pub fn error(&self, index: usize, text: &str, message: impl Into<String>) -> ParseError {
+        let line = &self.lines[index];
+        let start = text.as_ptr() as usize;
+        let base = line.as_ptr() as usize;
+        let offset = if start >= base && start + text.len() <= base + line.len() {
+            start - base
+        } else {
+            0
+        };
+
+        let source_line = &self.source[self.numbers[index]];
+        let file_offset = self.offsets[index] + offset;
+        let column = match source_line.get(..file_offset) {
+            Some(before) => before.chars().count() + 1,
+            // Only happens for text that is not a slice of the line.
+            None => source_line.chars().count() + 1,
+        };
+
+        ParseError {
+            file: self.name.clone(),
+            line: self.number(index),
+            column,
+            text: text.to_string(),
+            source_line: source_line.clone(),
+            message: message.into(),
+        }
+    }
+
+    /// Error for something missing after the last line.
+    pub fn missing(&self, message: impl Into<String>) -> ParseError {
+        ParseError {
+            file: self.name.clone(),
+            line: self.source.len() + 1,
+            column: 1,
+            text: String::new(),
+            source_line: String::new(),
+            message: message.into(),
+        }
+    }
+
+    /// Parse `text`, a slice of line `index`.
+    pub fn parse<T: FromStr>(&self, index: usize, text: &str) -> Result<T, ParseError>
+    where
+        T::Err: Display,
+    {
+        text.parse()
+            .map_err(|err| self.error(index, text, format!("cannot parse '{}': {}", text, err)))
+    }
+
+    /// Parse whitespace separated numbers, e.g. "79 14 55 13", from a slice of line `index`.
+    pub fn parse_numbers<T: FromStr>(&self, index: usize, text: &str) -> Result<Vec<T>, ParseError>
+    where
+        T::Err: Display,
+    {
+        text.split_whitespace()
+            .map(|s| self.parse(index, s))
+            .collect()
+    }
+}
+
+impl Deref for Lines {
+    type Target = [String];
+
+    fn deref(&self) -> &[String] {
+        &self.lines
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn error_column_counts_trimmed_whitespace() {
+        let lines = Lines::new("test", "\n  ab cd  \n", Normalize::TRIMMED);
+        let err = lines.error(0, &lines[0][3..], "bad");
+        assert_eq!((err.line, err.column, err.text.as_str()), (2, 6, "cd"));
+    }
+
+    #[test]
+    fn error_column_counts_characters() {
+        let lines = Lines::new("test", "äö x", Normalize::RAW);
+        let err = lines.error(0, &lines[0][5..], "bad");
+        assert_eq!(err.column, 4);
+    }
+
+    #[test]
+    fn number_counts_dropped_lines() {
+        let lines = Lines::new("test", "\n\na\n\nb", Normalize::TRIMMED);
+        assert_eq!((lines.number(0), lines.number(1)), (3, 5));
+    }
+
+    #[test]
+    fn error_outside_line_points_at_start() {
+        let lines = Lines::new("test", "abc", Normalize::RAW);
+        assert_eq!(lines.error(0, "b", "bad").column, 1);
+    }
+
+    #[test]
+    fn parse_numbers_reports_bad_number() {
+        let lines = Lines::new("test", "1 2 x3", Normalize::RAW);
+        let err = lines.parse_numbers::<u32>(0, &lines[0]).err().unwrap();
+        assert_eq!((err.column, err.text.as_str()), (5, "x3"));
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{Lines, Normalize, ParseError};
+use std::any::Any;
+use std::fmt;
+
+/// Answer to one part of a puzzle.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum Answer {
+    Number(i64),
+    /// The part has no solution implemented.
+    Unsolved,
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Answer::Number(n) => write!(f, "{}", n),
+            Answer::Unsolved => write!(f, "not solved"),
+        }
+    }
+}
+
+impl From<i32> for Answer {
+    fn from(value: i32) -> Answer {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<u32> for Answer {
+    fn from(value: u32) -> Answer {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<u64> for Answer {
+    fn from(value: u64) -> Answer {
+        Answer::Number(value as i64)
+    }
+}
+
+impl From<usize> for Answer {
+    fn from(value: usize) -> Answer {
+        Answer::Number(value as i64)
+    }
+}
+
+/// Solution of one day, split into parsing and the two parts.
+pub trait Solution {
+    /// Day of the calendar.
+    const DAY: u8;
+    /// How the lines of the input file are cleaned up before parsing.
+    const NORMALIZE: Normalize;
+
+    /// Puzzle input parsed into the form both parts work on.
+    type Input;
+
+    fn parse(input: &Lines) -> Result<Self::Input, ParseError>;
+
+    fn part1(_input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+
+    fn part2(_input: &Self::Input) -> Answer {
+        Answer::Unsolved
+    }
+}
+
+/// Object safe view of a Solution, so that every day can be kept in one list.
+/// The parsed input is passed between the stages as `dyn Any`.
+pub trait Solver {
+    fn day(&self) -> u8;
+    fn normalize(&self) -> Normalize;
+    fn parse(&self, input: &Lines) -> Result<Box<dyn Any>, ParseError>;
+    fn part1(&self, input: &dyn Any) -> Answer;
+    fn part2(&self, input: &dyn Any) -> Answer;
+
+    /// Run part 1 or 2 on parsed input.
+    fn part(&self, input: &dyn Any, part: u8) -> Answer {
+        match part {
+            1 => self.part1(input),
+            2 => self.part2(input),
+            _ => Answer::Unsolved,
+        }
+    }
+}
+
+impl<S: Solution> Solver for S
+where
+    S::Input: 'static,
+{
+    fn day(&self) -> u8 {
+        S::DAY
+    }
+
+    fn normalize(&self) -> Normalize {
+        S::NORMALIZE
+    }
+
+    fn parse(&self, input: &Lines) -> Result<Box<dyn Any>, ParseError> {
+        Ok(Box::new(S::parse(input)?))
+    }
+
+    fn part1(&self, input: &dyn Any) -> Answer {
+        S::part1(input.downcast_ref().expect("input parsed by another day"))
+    }
+
+    fn part2(&self, input: &dyn Any) -> Answer {
+        S::part2(input.downcast_ref().expect("input parsed by another day"))
+    }
+}
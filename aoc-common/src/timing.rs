@@ -0,0 +1,165 @@
+use crate::{Answer, Lines, ParseError, Solver};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// How many times a stage is run before and while it is measured.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Iterations {
+    pub warmup: usize,
+    pub measured: usize,
+}
+
+impl Iterations {
+    /// Run every stage once, without warm-up.
+    pub const ONCE: Iterations = Iterations {
+        warmup: 0,
+        measured: 1,
+    };
+}
+
+/// Durations of the measured runs of one stage.
+#[derive(Clone, Debug, Default)]
+pub struct Timings {
+    /// Sorted from fastest to slowest.
+    samples: Vec<Duration>,
+}
+
+impl Timings {
+    pub fn new(mut samples: Vec<Duration>) -> Timings {
+        samples.sort();
+        Timings { samples }
+    }
+
+    pub fn min(&self) -> Duration {
+        self.samples.first().copied().unwrap_or_default()
+    }
+
+    pub fn max(&self) -> Duration {
+        self.samples.last().copied().unwrap_or_default()
+    }
+
+    pub fn median(&self) -> Duration {
+        let n = self.samples.len();
+        if n == 0 {
+            return Duration::ZERO;
+        }
+        if n % 2 == 1 {
+            return self.samples[n / 2];
+        }
+        (self.samples[n / 2 - 1] + self.samples[n / 2]) / 2
+    }
+
+    /// Timings of running this stage and then other, e.g. the stages of a day
+    /// or the same stage of every day. Adds up min, median and max separately.
+    pub fn add(&self, other: &Timings) -> Timings {
+        Timings {
+            samples: vec![
+                self.min() + other.min(),
+                self.median() + other.median(),
+                self.max() + other.max(),
+            ],
+        }
+    }
+}
+
+/// Run f for the warm-up iterations, then time it for the measured ones.
+/// Returns the result of the last run. At least one run is always measured.
+pub fn measure<T>(iterations: Iterations, mut f: impl FnMut() -> T) -> (T, Timings) {
+    for _ in 0..iterations.warmup {
+        black_box(f());
+    }
+
+    let mut samples: Vec<Duration> = Vec::new();
+    let mut res: T;
+    loop {
+        let start = Instant::now();
+        res = black_box(f());
+        samples.push(start.elapsed());
+
+        if samples.len() >= iterations.measured {
+            break;
+        }
+    }
+    (res, Timings::new(samples))
+}
+
+/// Answers and timings of every stage of one day on one input.
+pub struct DayTimings {
+    pub day: u8,
+    pub parse: Timings,
+    /// Answer and timings of part 1 and part 2.
+    pub parts: Vec<(u8, Answer, Timings)>,
+}
+
+impl DayTimings {
+    /// Timings of parsing and all the parts together.
+    pub fn total(&self) -> Timings {
+        let mut total = self.parse.clone();
+        for (_, _, timings) in self.parts.iter() {
+            total = total.add(timings);
+        }
+        total
+    }
+}
+
+/// Parse input with day and solve the given parts, timing every stage.
+pub fn time_day(
+    day: &dyn Solver,
+    input: &Lines,
+    parts: &[u8],
+    iterations: Iterations,
+) -> Result<DayTimings, ParseError> {
+    let (parsed, parse) = measure(iterations, || day.parse(input));
+    let parsed = parsed?;
+
+    let mut res = DayTimings {
+        day: day.day(),
+        parse,
+        parts: Vec::new(),
+    };
+    for part in parts {
+        let (answer, timings) = measure(iterations, || day.part(parsed.as_ref(), *part));
+        res.parts.push((*part, answer, timings));
+    }
+    Ok(res)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn ms(values: &[u64]) -> Timings {
+        Timings::new(values.iter().map(|v| Duration::from_millis(*v)).collect())
+    }
+
+    #[test]
+    fn min_median_max() {
+        let timings = ms(&[5, 1, 3]);
+        assert_eq!(timings.min(), Duration::from_millis(1));
+        assert_eq!(timings.median(), Duration::from_millis(3));
+        assert_eq!(timings.max(), Duration::from_millis(5));
+        assert_eq!(ms(&[4, 1, 2, 3]).median(), Duration::from_micros(2500));
+    }
+
+    #[test]
+    fn add_sums_each_statistic() {
+        let total = ms(&[1, 2, 9]).add(&ms(&[10, 20, 30]));
+        assert_eq!(total.min(), Duration::from_millis(11));
+        assert_eq!(total.median(), Duration::from_millis(22));
+        assert_eq!(total.max(), Duration::from_millis(39));
+    }
+
+    #[test]
+    fn measure_runs_warmup_and_iterations() {
+        let mut runs = 0;
+        let iterations = Iterations {
+            warmup: 2,
+            measured: 3,
+        };
+        let (res, timings) = measure(iterations, || {
+            runs += 1;
+            runs
+        });
+        assert_eq!((res, runs, timings.samples.len()), (5, 5, 3));
+    }
+}
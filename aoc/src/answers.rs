@@ -0,0 +1,95 @@
+use serde::Deserialize;
+use std::fs::read_to_string;
+use std::path::Path;
+
+/// Known-correct answers, kept in answers.toml:
+///
+/// ```toml
+/// [[answer]]
+/// day = 5
+/// input = "inputs/2023/day05.txt"
+/// part1 = 35
+/// part2 = 46
+/// ```
+#[derive(Deserialize, Default)]
+#[serde(deny_unknown_fields)]
+pub struct Answers {
+    #[serde(default, rename = "answer")]
+    records: Vec<Record>,
+}
+
+/// Answers of one day for one input file. A part without an answer is unknown.
+#[derive(Deserialize)]
+#[serde(deny_unknown_fields)]
+struct Record {
+    day: u8,
+    input: String,
+    part1: Option<i64>,
+    part2: Option<i64>,
+}
+
+impl Answers {
+    /// Read the registry from filename.
+    /// A missing file is an empty registry, unless required.
+    pub fn load(filename: &str, required: bool) -> Result<Answers, String> {
+        if !required && !Path::new(filename).exists() {
+            return Ok(Answers::default());
+        }
+
+        let text = read_to_string(filename).map_err(|err| format!("Cannot read {}: {}", filename, err))?;
+        toml::from_str(&text).map_err(|err| format!("Invalid {}: {}", filename, err))
+    }
+
+    /// Input files with recorded answers for day.
+    pub fn inputs(&self, day: u8) -> Vec<&str> {
+        let mut res: Vec<&str> = Vec::new();
+        for record in self.records.iter() {
+            if record.day == day && !res.contains(&record.input.as_str()) {
+                res.push(&record.input);
+            }
+        }
+        res
+    }
+
+    /// Recorded answer of part for day on input.
+    pub fn expected(&self, day: u8, input: &str, part: u8) -> Option<i64> {
+        self.records
+            .iter()
+            .filter(|record| record.day == day && record.input == input)
+            .find_map(|record| match part {
+                1 => record.part1,
+                2 => record.part2,
+                _ => None,
+            })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn expected_answers_by_day_input_and_part() {
+        let answers: Answers = toml::from_str(
+            r#"
+            [[answer]]
+            day = 5
+            input = "inputs/day05.txt"
+            part1 = 35
+
+            [[answer]]
+            day = 5
+            input = "alice.txt"
+            part1 = 10
+            part2 = 20
+            "#,
+        )
+        .unwrap();
+
+        assert_eq!(answers.inputs(5), vec!["inputs/day05.txt", "alice.txt"]);
+        assert_eq!(answers.expected(5, "inputs/day05.txt", 1), Some(35));
+        assert_eq!(answers.expected(5, "inputs/day05.txt", 2), None);
+        assert_eq!(answers.expected(5, "alice.txt", 2), Some(20));
+        assert_eq!(answers.expected(6, "alice.txt", 2), None);
+    }
+}
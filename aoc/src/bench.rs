@@ -0,0 +1,79 @@
+use crate::output::Format;
+use crate::{inputs, time_input, Failure, RunArgs};
+use aoc_common::{input_name, print_table, Iterations, Timings};
+use std::time::Duration;
+
+/// Iterations of bench unless given with --warmup and --iterations.
+pub const ITERATIONS: Iterations = Iterations {
+    warmup: 3,
+    measured: 10,
+};
+
+fn format_duration(duration: Duration) -> String {
+    format!("{:.2?}", duration)
+}
+
+fn timing_row(day: &str, input: &str, stage: &str, timings: &Timings) -> Vec<String> {
+    vec![
+        day.to_string(),
+        input.to_string(),
+        stage.to_string(),
+        format_duration(timings.min()),
+        format_duration(timings.median()),
+        format_duration(timings.max()),
+    ]
+}
+
+/// Time every stage of the selected days and print a table of the timings,
+/// with totals per day and, when there is more than one day, for the whole calendar.
+/// The table is always text, other formats are a usage error.
+pub fn bench(args: RunArgs) -> Result<(), Failure> {
+    if args.format != Format::Text {
+        return Err("bench only prints a table of timings, --format is for run".to_string().into());
+    }
+
+    let header = ["Day", "Input", "Stage", "Min", "Median", "Max"];
+    let mut table: Vec<Vec<String>> = vec![header.map(|s| s.to_string()).to_vec()];
+
+    let mut calendar_parse = Timings::default();
+    let mut calendar_parts: [Timings; 2] = Default::default();
+    let mut timed: usize = 0;
+
+    for (day, filename) in inputs(&args)?.iter() {
+        let timings = time_input(*day, filename, &args)?;
+        let number = day.day().to_string();
+        let input = input_name(filename);
+
+        table.push(timing_row(&number, input, "parse", &timings.parse));
+        for (part, _, part_timings) in timings.parts.iter() {
+            let stage = format!("part {}", part);
+            table.push(timing_row(&number, input, &stage, part_timings));
+
+            let total = &mut calendar_parts[*part as usize - 1];
+            *total = total.add(part_timings);
+        }
+        table.push(timing_row(&number, input, "total", &timings.total()));
+
+        calendar_parse = calendar_parse.add(&timings.parse);
+        timed += 1;
+    }
+
+    if timed > 1 {
+        table.push(timing_row("all", "", "parse", &calendar_parse));
+        let mut total = calendar_parse.clone();
+        for (i, part_timings) in calendar_parts.iter().enumerate() {
+            if args.part.is_none_or(|part| part as usize == i + 1) {
+                table.push(timing_row("all", "", &format!("part {}", i + 1), part_timings));
+                total = total.add(part_timings);
+            }
+        }
+        table.push(timing_row("all", "", "total", &total));
+    }
+
+    println!(
+        "{} warm-up and {} measured iterations per stage\n",
+        args.iterations.warmup, args.iterations.measured
+    );
+    print_table(&table);
+    Ok(())
+}
@@ -0,0 +1,20 @@
+use aoc_common::Solver;
+use day01::Day01;
+use day02::Day02;
+use day03::Day03;
+use day04::Day04;
+use day05::Day05;
+use day06::Day06;
+use day07::Day07;
+use day08::Day08;
+use day09::Day09;
+use day10::Day10;
+use day11::Day11;
+
+pub const DAYS: [&dyn Solver; 11] = [
+    &Day01, &Day02, &Day03, &Day04, &Day05, &Day06, &Day07, &Day08, &Day09, &Day10, &Day11,
+];
+
+pub fn get_day(number: u8) -> Option<&'static dyn Solver> {
+    DAYS.iter().find(|day| day.day() == number).copied()
+}
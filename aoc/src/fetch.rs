@@ -0,0 +1,243 @@
+use std::fs::{create_dir_all, read_to_string, rename, write};
+use std::path::{Path, PathBuf};
+use std::thread::sleep;
+use std::time::{Duration, Instant};
+
+/// Year of the calendar the days belong to.
+pub const YEAR: u16 = 2023;
+
+/// Where puzzle inputs come from when they are not in the cache.
+pub trait Fetch {
+    fn fetch(&mut self, year: u16, day: u8) -> Result<String, String>;
+}
+
+/// Downloads inputs over HTTP from `<base_url>/<year>/day/<day>/input`,
+/// logged in with a session token. Waits between requests so the server is not hammered.
+pub struct HttpFetcher {
+    base_url: String,
+    session: String,
+    interval: Duration,
+    last_request: Option<Instant>,
+}
+
+impl HttpFetcher {
+    pub const BASE_URL: &'static str = "https://adventofcode.com";
+    pub const INTERVAL: Duration = Duration::from_secs(3);
+
+    pub fn new(base_url: &str, session: &str, interval: Duration) -> HttpFetcher {
+        HttpFetcher {
+            base_url: base_url.trim_end_matches('/').to_string(),
+            session: session.to_string(),
+            interval,
+            last_request: None,
+        }
+    }
+
+    /// Read the session token from filename, e.g. a `.session` file holding the cookie value.
+    pub fn read_session(filename: &str) -> Result<String, String> {
+        let session = read_to_string(filename)
+            .map_err(|err| format!("Cannot read session token from {}: {}", filename, err))?;
+        let session = session.trim();
+        if session.is_empty() {
+            return Err(format!("Session token file {} is empty", filename));
+        }
+        Ok(session.to_string())
+    }
+
+    fn throttle(&mut self) {
+        if let Some(last) = self.last_request {
+            let elapsed = last.elapsed();
+            if elapsed < self.interval {
+                sleep(self.interval - elapsed);
+            }
+        }
+        self.last_request = Some(Instant::now());
+    }
+}
+
+impl Fetch for HttpFetcher {
+    fn fetch(&mut self, year: u16, day: u8) -> Result<String, String> {
+        self.throttle();
+
+        let url = format!("{}/{}/day/{}/input", self.base_url, year, day);
+        let response = ureq::get(&url)
+            .set("Cookie", &format!("session={}", self.session))
+            .set("User-Agent", "aoc input fetcher")
+            .call()
+            .map_err(|err| format!("Cannot fetch the input of day {}: {}", day, err))?;
+
+        response
+            .into_string()
+            .map_err(|err| format!("Cannot read the response from {}: {}", url, err))
+    }
+}
+
+/// Directory of cached inputs, laid out as `<dir>/<year>/dayNN.txt`.
+pub struct InputCache {
+    dir: PathBuf,
+}
+
+impl InputCache {
+    pub fn new(dir: &str) -> InputCache {
+        InputCache {
+            dir: PathBuf::from(dir),
+        }
+    }
+
+    /// Where the input of day is cached.
+    pub fn path(&self, day: u8) -> PathBuf {
+        self.dir.join(YEAR.to_string()).join(format!("day{:02}.txt", day))
+    }
+
+    /// Input of day in the cache. Falls back to `<dir>/dayNN.txt`, where
+    /// inputs used to be kept, and to the cache path if neither exists.
+    pub fn find(&self, day: u8) -> PathBuf {
+        let path = self.path(day);
+        let old = self.dir.join(format!("day{:02}.txt", day));
+        if !path.exists() && old.exists() {
+            return old;
+        }
+        path
+    }
+
+    /// Input of day, fetched into the cache first if it is not there yet.
+    /// An input that is already cached is never fetched again.
+    pub fn resolve(&self, day: u8, fetcher: &mut dyn Fetch) -> Result<PathBuf, String> {
+        let path = self.find(day);
+        if path.exists() {
+            return Ok(path);
+        }
+
+        let text = fetcher.fetch(YEAR, day)?;
+        self.store(&path, &text)?;
+        Ok(path)
+    }
+
+    /// Write through a temporary file, so an interrupted download never looks cached.
+    fn store(&self, path: &Path, text: &str) -> Result<(), String> {
+        let error = |err: std::io::Error| format!("Cannot write {}: {}", path.display(), err);
+
+        if let Some(parent) = path.parent() {
+            create_dir_all(parent).map_err(error)?;
+        }
+        let partial = path.with_extension("txt.part");
+        write(&partial, text).map_err(error)?;
+        rename(&partial, path).map_err(error)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::remove_dir_all;
+    use std::io::{BufRead, BufReader, Write};
+    use std::net::TcpListener;
+    use std::sync::{Arc, Mutex};
+    use std::thread;
+
+    /// Local server standing in for the puzzle site. Answers every input request with
+    /// "input of day N", except day 25 which is not found, and remembers the requests.
+    fn mock_server() -> (String, Arc<Mutex<Vec<String>>>) {
+        let listener = TcpListener::bind("127.0.0.1:0").unwrap();
+        let base_url = format!("http://{}", listener.local_addr().unwrap());
+        let requests: Arc<Mutex<Vec<String>>> = Arc::new(Mutex::new(Vec::new()));
+
+        let seen = Arc::clone(&requests);
+        thread::spawn(move || {
+            for stream in listener.incoming() {
+                let mut stream = stream.unwrap();
+                let mut request = String::new();
+                let mut reader = BufReader::new(&stream);
+                loop {
+                    let mut line = String::new();
+                    if reader.read_line(&mut line).unwrap() == 0 || line == "\r\n" {
+                        break;
+                    }
+                    request.push_str(&line);
+                }
+
+                let path = request.split_whitespace().nth(1).unwrap_or_default().to_string();
+                seen.lock().unwrap().push(request);
+
+                let (status, body) = match path.split('/').nth(3) {
+                    Some("25") | None => ("404 Not Found", "not found".to_string()),
+                    Some(day) => ("200 OK", format!("input of day {}\n", day)),
+                };
+                let response = format!(
+                    "HTTP/1.1 {}\r\nContent-Length: {}\r\nConnection: close\r\n\r\n{}",
+                    status,
+                    body.len(),
+                    body
+                );
+                stream.write_all(response.as_bytes()).unwrap();
+            }
+        });
+        (base_url, requests)
+    }
+
+    fn empty_dir(name: &str) -> String {
+        let dir = temp_dir().join(format!("aoc-fetch-{}-{}", std::process::id(), name));
+        let _ = remove_dir_all(&dir);
+        dir.display().to_string()
+    }
+
+    #[test]
+    fn fetch_sends_session_cookie() {
+        let (base_url, requests) = mock_server();
+        let mut fetcher = HttpFetcher::new(&base_url, "secret", Duration::ZERO);
+
+        assert_eq!(fetcher.fetch(2023, 8).unwrap(), "input of day 8\n");
+
+        let requests = requests.lock().unwrap();
+        assert!(requests[0].starts_with("GET /2023/day/8/input "));
+        assert!(requests[0].contains("session=secret"));
+    }
+
+    #[test]
+    fn resolve_never_fetches_cached_input() {
+        let (base_url, requests) = mock_server();
+        let mut fetcher = HttpFetcher::new(&base_url, "secret", Duration::ZERO);
+        let cache = InputCache::new(&empty_dir("cached"));
+
+        let path = cache.resolve(8, &mut fetcher).unwrap();
+        assert_eq!(path, cache.path(8));
+        assert_eq!(read_to_string(&path).unwrap(), "input of day 8\n");
+
+        cache.resolve(8, &mut fetcher).unwrap();
+        assert_eq!(requests.lock().unwrap().len(), 1);
+    }
+
+    #[test]
+    fn failed_fetch_is_not_cached() {
+        let (base_url, _) = mock_server();
+        let mut fetcher = HttpFetcher::new(&base_url, "secret", Duration::ZERO);
+        let cache = InputCache::new(&empty_dir("failed"));
+
+        assert!(cache.resolve(25, &mut fetcher).is_err());
+        assert!(!cache.path(25).exists());
+    }
+
+    #[test]
+    fn fetches_are_throttled() {
+        let (base_url, _) = mock_server();
+        let interval = Duration::from_millis(200);
+        let mut fetcher = HttpFetcher::new(&base_url, "secret", interval);
+
+        let start = Instant::now();
+        fetcher.fetch(2023, 1).unwrap();
+        fetcher.fetch(2023, 2).unwrap();
+        assert!(start.elapsed() >= interval);
+    }
+
+    #[test]
+    fn find_falls_back_to_old_layout() {
+        let dir = empty_dir("old");
+        let cache = InputCache::new(&dir);
+        assert_eq!(cache.find(3), cache.path(3));
+
+        create_dir_all(&dir).unwrap();
+        write(Path::new(&dir).join("day03.txt"), "old").unwrap();
+        assert_eq!(cache.find(3), Path::new(&dir).join("day03.txt"));
+    }
+}
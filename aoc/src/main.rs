@@ -0,0 +1,338 @@
+mod answers;
+mod bench;
+mod days;
+mod fetch;
+mod output;
+mod verify;
+
+use aoc_common::{input_name, parse_inputfile, time_day, DayTimings, Iterations, ParseError, Solver, STDIN};
+use bench::bench;
+use days::{get_day, DAYS};
+use fetch::{HttpFetcher, InputCache};
+use output::{Format, Output, Record};
+use verify::verify;
+use std::env;
+use std::io::{self, IsTerminal};
+use std::path::Path;
+use std::process::exit;
+
+const USAGE: &str = "Usage:
+    aoc run --day <N> [--part <1|2>] [--format <FORMAT>] [FILE...]
+    aoc run --all [--inputs <DIR>] [--format <FORMAT>]
+    aoc bench (--day <N> [FILE...] | --all) [--warmup <N>] [--iterations <N>]
+    aoc verify [--day <N>] [--inputs <DIR>] [--answers <FILE>]
+
+Fetching options of run and bench:
+    --fetch                 Download missing inputs into the cache
+    --base-url <URL>        Server to download from [https://adventofcode.com]
+    --session-file <FILE>   File holding the session token [.session]
+
+FILE defaults to the cached input <DIR>/2023/dayNN.txt, or <DIR>/dayNN.txt,
+with DIR defaulting to 'inputs'. A FILE of '-' reads standard input. With
+several files every answer is labelled with the file it came from.
+
+FORMAT is one of text (the default), json, csv or plain. json and csv give
+one record per day and part with the answer, the time it took in nanoseconds
+and the input file, plain gives only the bare answers.
+
+bench takes the same options as run, except --format, and times parsing,
+part 1 and part 2 separately, reporting min, median and max over the
+measured iterations.
+
+verify re-runs the days on every input with answers recorded in
+answers.toml, and on the default inputs, and reports pass/fail/unknown.";
+
+struct RunArgs {
+    day: Option<u8>,
+    part: Option<u8>,
+    all: bool,
+    inputs: String,
+    filenames: Vec<String>,
+    iterations: Iterations,
+    format: Format,
+    fetch: bool,
+    base_url: String,
+    session_file: String,
+}
+
+/// Parse the arguments following 'run' or 'bench'.
+fn parse_run_args(args: &[String], iterations: Iterations) -> Result<RunArgs, String> {
+    let mut res = RunArgs {
+        day: None,
+        part: None,
+        all: false,
+        inputs: "inputs".to_string(),
+        filenames: Vec::new(),
+        iterations,
+        format: Format::Text,
+        fetch: false,
+        base_url: HttpFetcher::BASE_URL.to_string(),
+        session_file: ".session".to_string(),
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => res.day = Some(parse_value(arg, args.next())?),
+            "--part" => res.part = Some(parse_value(arg, args.next())?),
+            "--inputs" => res.inputs = parse_value(arg, args.next())?,
+            "--all" => res.all = true,
+            "--format" => res.format = parse_value(arg, args.next())?,
+            "--warmup" => res.iterations.warmup = parse_value(arg, args.next())?,
+            "--iterations" => res.iterations.measured = parse_value(arg, args.next())?,
+            "--fetch" => res.fetch = true,
+            "--base-url" => res.base_url = parse_value(arg, args.next())?,
+            "--session-file" => res.session_file = parse_value(arg, args.next())?,
+            _ if arg.starts_with("--") => return Err(format!("Unknown option {}", arg)),
+            _ => res.filenames.push(arg.to_string()),
+        }
+    }
+
+    if res.all == res.day.is_some() {
+        return Err("Give either --day or --all".to_string());
+    }
+    if res.all && !res.filenames.is_empty() {
+        return Err("Input files can only be given with --day".to_string());
+    }
+    if res.part.is_some_and(|part| part != 1 && part != 2) {
+        return Err("--part must be 1 or 2".to_string());
+    }
+    if res.iterations.measured == 0 {
+        return Err("--iterations must be at least 1".to_string());
+    }
+    Ok(res)
+}
+
+struct VerifyArgs {
+    day: Option<u8>,
+    inputs: String,
+    answers: Option<String>,
+}
+
+/// Parse the arguments following 'verify'.
+fn parse_verify_args(args: &[String]) -> Result<VerifyArgs, String> {
+    let mut res = VerifyArgs {
+        day: None,
+        inputs: "inputs".to_string(),
+        answers: None,
+    };
+
+    let mut args = args.iter();
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--day" => res.day = Some(parse_value(arg, args.next())?),
+            "--inputs" => res.inputs = parse_value(arg, args.next())?,
+            "--answers" => res.answers = Some(parse_value(arg, args.next())?),
+            _ => return Err(format!("Unknown argument {}", arg)),
+        }
+    }
+
+    if let Some(number) = res.day {
+        get_day(number).ok_or(format!("Day {} is not solved", number))?;
+    }
+    Ok(res)
+}
+
+fn parse_value<T: std::str::FromStr>(option: &str, value: Option<&String>) -> Result<T, String> {
+    match value.map(|v| v.parse()) {
+        Some(Ok(v)) => Ok(v),
+        Some(Err(_)) => Err(format!("Invalid value for {}", option)),
+        None => Err(format!("Missing value for {}", option)),
+    }
+}
+
+/// Where the input of a day is found when no file is given.
+fn default_input(inputs: &str, day: u8) -> String {
+    InputCache::new(inputs).find(day).display().to_string()
+}
+
+/// Why the runner stopped.
+enum Failure {
+    /// Bad command line, exit code 2.
+    Usage(String),
+    /// Input that cannot be read, exit code 1.
+    Read(String),
+    /// Malformed puzzle input, exit code 1.
+    Input(ParseError),
+    /// Answers that do not match the recorded ones, exit code 1.
+    Verify,
+}
+
+impl From<String> for Failure {
+    fn from(message: String) -> Failure {
+        Failure::Usage(message)
+    }
+}
+
+impl From<ParseError> for Failure {
+    fn from(err: ParseError) -> Failure {
+        Failure::Input(err)
+    }
+}
+
+/// Read filename and solve the parts of day on it, timing every stage.
+fn time_input(day: &dyn Solver, filename: &str, args: &RunArgs) -> Result<DayTimings, Failure> {
+    let parts: Vec<u8> = match args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+
+    let lines = parse_inputfile(filename, day.normalize()).map_err(Failure::Read)?;
+    Ok(time_day(day, &lines, &parts, args.iterations)?)
+}
+
+/// Input of day when no file is given, fetched into the cache with --fetch.
+/// The fetcher is only created, and the session token read, once something is missing.
+fn resolve_input(args: &RunArgs, day: u8, fetcher: &mut Option<HttpFetcher>) -> Result<String, Failure> {
+    let cache = InputCache::new(&args.inputs);
+    if !args.fetch || cache.find(day).exists() {
+        return Ok(default_input(&args.inputs, day));
+    }
+
+    if fetcher.is_none() {
+        let session = HttpFetcher::read_session(&args.session_file).map_err(Failure::Read)?;
+        *fetcher = Some(HttpFetcher::new(&args.base_url, &session, HttpFetcher::INTERVAL));
+    }
+    let path = cache.resolve(day, fetcher.as_mut().unwrap()).map_err(Failure::Read)?;
+    Ok(path.display().to_string())
+}
+
+/// Input of day when no file is given: standard input when something is piped into it
+/// and nothing is cached, otherwise the cached input, fetched with --fetch.
+fn day_input(args: &RunArgs, day: u8, piped: bool, fetcher: &mut Option<HttpFetcher>) -> Result<String, Failure> {
+    if piped && !InputCache::new(&args.inputs).find(day).exists() {
+        return Ok(STDIN.to_string());
+    }
+    resolve_input(args, day, fetcher)
+}
+
+/// Days and input files selected by the arguments.
+/// With --all, days without an input in the inputs directory are skipped.
+fn inputs(args: &RunArgs) -> Result<Vec<(&'static dyn Solver, String)>, Failure> {
+    let mut res: Vec<(&'static dyn Solver, String)> = Vec::new();
+    let mut fetcher: Option<HttpFetcher> = None;
+
+    if args.all {
+        for day in DAYS.iter() {
+            let filename = resolve_input(args, day.day(), &mut fetcher)?;
+            if Path::new(&filename).exists() {
+                res.push((*day, filename));
+            } else {
+                eprintln!("Day {}: no input at {}", day.day(), filename);
+            }
+        }
+        return Ok(res);
+    }
+
+    let number = args.day.unwrap();
+    let day = get_day(number).ok_or(format!("Day {} is not solved", number))?;
+    let mut filenames: Vec<String> = args.filenames.clone();
+    if filenames.is_empty() {
+        let piped = !io::stdin().is_terminal();
+        filenames.push(day_input(args, number, piped, &mut fetcher)?);
+    }
+
+    for filename in filenames {
+        if filename != STDIN && !Path::new(&filename).exists() {
+            return Err(format!("No such file: {}", filename).into());
+        }
+        res.push((day, filename));
+    }
+    Ok(res)
+}
+
+fn run(args: RunArgs) -> Result<(), Failure> {
+    let inputs = inputs(&args)?;
+    // Several files of one day are told apart by their name.
+    let mut output = Output::new(args.format, !args.all && inputs.len() > 1);
+
+    for (day, filename) in inputs.iter() {
+        let timings = time_input(*day, filename, &args)?;
+
+        for (part, answer, part_timings) in timings.parts.iter() {
+            let input = input_name(filename);
+            output.record(Record::new(day.day(), *part, answer, part_timings.median(), input));
+        }
+    }
+    output.finish();
+    Ok(())
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let res = match args.first().map(|s| s.as_str()) {
+        Some("run") => parse_run_args(&args[1..], Iterations::ONCE)
+            .map_err(Failure::from)
+            .and_then(run),
+        Some("bench") => parse_run_args(&args[1..], bench::ITERATIONS)
+            .map_err(Failure::from)
+            .and_then(bench),
+        Some("verify") => parse_verify_args(&args[1..])
+            .map_err(Failure::from)
+            .and_then(verify),
+        _ => Err(USAGE.to_string().into()),
+    };
+
+    match res {
+        Ok(()) => {}
+        Err(Failure::Usage(message)) => {
+            eprintln!("{}", message);
+            exit(2);
+        }
+        Err(Failure::Read(message)) => {
+            eprintln!("{}", message);
+            exit(1);
+        }
+        Err(Failure::Input(err)) => {
+            eprintln!("{}", err);
+            exit(1);
+        }
+        Err(Failure::Verify) => exit(1),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::env::temp_dir;
+    use std::fs::{create_dir_all, remove_dir_all, write};
+
+    fn run_args(args: &[&str]) -> RunArgs {
+        let args: Vec<String> = args.iter().map(|arg| arg.to_string()).collect();
+        parse_run_args(&args, Iterations::ONCE).unwrap()
+    }
+
+    fn inputs_dir(name: &str) -> String {
+        let dir = temp_dir().join(format!("aoc-main-{}-{}", std::process::id(), name));
+        let _ = remove_dir_all(&dir);
+        dir.display().to_string()
+    }
+
+    #[test]
+    fn piped_input_is_read_when_nothing_is_cached() {
+        let dir = inputs_dir("piped");
+        let args = run_args(&["--day", "1", "--inputs", &dir]);
+
+        assert_eq!(day_input(&args, 1, true, &mut None).ok(), Some(STDIN.to_string()));
+        assert_eq!(day_input(&args, 1, false, &mut None).ok(), Some(default_input(&dir, 1)));
+    }
+
+    #[test]
+    fn cached_input_is_preferred_to_piped_input() {
+        let dir = inputs_dir("cached");
+        create_dir_all(Path::new(&dir).join("2023")).unwrap();
+        let cached = Path::new(&dir).join("2023").join("day01.txt");
+        write(&cached, "1abc2\n").unwrap();
+        let args = run_args(&["--day", "1", "--inputs", &dir]);
+
+        assert_eq!(day_input(&args, 1, true, &mut None).ok(), Some(cached.display().to_string()));
+        remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn bench_rejects_format() {
+        let args = run_args(&["--day", "1", "--format", "json"]);
+        assert!(matches!(bench(args), Err(Failure::Usage(_))));
+    }
+}
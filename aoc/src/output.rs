@@ -0,0 +1,124 @@
+use aoc_common::{csv_field, Answer};
+use serde::Serialize;
+use std::str::FromStr;
+use std::time::Duration;
+
+/// How `aoc run` prints the answers.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum Format {
+    /// "Day 5 part 1: 35", for reading.
+    Text,
+    /// One JSON array of records.
+    Json,
+    /// One CSV row per record, with a header.
+    Csv,
+    /// Only the bare answer, one per line.
+    Plain,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            "csv" => Ok(Format::Csv),
+            "plain" => Ok(Format::Plain),
+            _ => Err(format!("Unknown format {}", s)),
+        }
+    }
+}
+
+/// Answer of one part of a day on one input.
+#[derive(Serialize)]
+pub struct Record {
+    pub day: u8,
+    pub part: u8,
+    /// None if the part is not solved.
+    pub answer: Option<i64>,
+    pub elapsed_ns: u128,
+    pub input: String,
+}
+
+impl Record {
+    pub fn new(day: u8, part: u8, answer: &Answer, elapsed: Duration, input: &str) -> Record {
+        Record {
+            day,
+            part,
+            answer: match answer {
+                Answer::Number(n) => Some(*n),
+                Answer::Unsolved => None,
+            },
+            elapsed_ns: elapsed.as_nanos(),
+            input: input.to_string(),
+        }
+    }
+}
+
+/// Prints records as they come, except JSON which is printed as a whole by `finish`.
+pub struct Output {
+    format: Format,
+    /// Name the input in text output, to tell several inputs apart.
+    label: bool,
+    records: Vec<Record>,
+}
+
+impl Output {
+    pub fn new(format: Format, label: bool) -> Output {
+        if format == Format::Csv {
+            println!("day,part,answer,elapsed_ns,input");
+        }
+        Output {
+            format,
+            label,
+            records: Vec::new(),
+        }
+    }
+
+    pub fn record(&mut self, record: Record) {
+        let answer: String = match record.answer {
+            Some(n) => n.to_string(),
+            None => String::new(),
+        };
+
+        match self.format {
+            Format::Text => {
+                let answer = record.answer.map_or(Answer::Unsolved, Answer::Number);
+                if self.label {
+                    println!("Day {} part {} ({}): {}", record.day, record.part, record.input, answer);
+                } else {
+                    println!("Day {} part {}: {}", record.day, record.part, answer);
+                }
+            }
+            Format::Json => self.records.push(record),
+            Format::Csv => println!(
+                "{},{},{},{},{}",
+                record.day,
+                record.part,
+                answer,
+                record.elapsed_ns,
+                csv_field(&record.input)
+            ),
+            Format::Plain => println!("{}", answer),
+        }
+    }
+
+    pub fn finish(self) {
+        if self.format == Format::Json {
+            println!("{}", serde_json::to_string_pretty(&self.records).unwrap());
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json_record() {
+        let record = Record::new(5, 1, &Answer::Number(35), Duration::from_nanos(1200), "in.txt");
+        let json = serde_json::to_string(&record).unwrap();
+        assert_eq!(json, r#"{"day":5,"part":1,"answer":35,"elapsed_ns":1200,"input":"in.txt"}"#);
+    }
+}
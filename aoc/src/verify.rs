@@ -0,0 +1,141 @@
+use crate::answers::Answers;
+use crate::days::DAYS;
+use crate::{default_input, Failure, VerifyArgs};
+use aoc_common::{parse_inputfile, print_table, Answer, Solver};
+use std::path::Path;
+
+/// Outcome of checking one answer.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum Status {
+    Pass,
+    Fail,
+    /// No answer recorded.
+    Unknown,
+    /// The input could not be read or parsed.
+    Error,
+}
+
+impl Status {
+    fn name(self) -> &'static str {
+        match self {
+            Status::Pass => "pass",
+            Status::Fail => "fail",
+            Status::Unknown => "unknown",
+            Status::Error => "error",
+        }
+    }
+}
+
+/// One line of the report table.
+struct Row {
+    day: u8,
+    part: u8,
+    input: String,
+    answer: String,
+    expected: String,
+    status: Status,
+}
+
+fn check(answer: &Answer, expected: Option<i64>) -> Status {
+    match (answer, expected) {
+        (_, None) => Status::Unknown,
+        (Answer::Number(n), Some(e)) if *n == e => Status::Pass,
+        _ => Status::Fail,
+    }
+}
+
+/// Run both parts of day on input and compare them to the recorded answers.
+fn verify_input(day: &dyn Solver, input: &str, answers: &Answers) -> Vec<Row> {
+    let parsed = match parse_inputfile(input, day.normalize()) {
+        Ok(lines) => day.parse(&lines).map_err(|err| err.to_string()),
+        Err(message) => Err(message),
+    };
+
+    let mut rows: Vec<Row> = Vec::new();
+    for part in [1, 2] {
+        let expected = answers.expected(day.day(), input, part);
+        let mut row = Row {
+            day: day.day(),
+            part,
+            input: input.to_string(),
+            answer: String::new(),
+            expected: expected.map(|e| e.to_string()).unwrap_or_default(),
+            status: Status::Error,
+        };
+
+        match &parsed {
+            Ok(parsed) => {
+                let answer = day.part(parsed.as_ref(), part);
+                row.status = check(&answer, expected);
+                row.answer = answer.to_string();
+            }
+            Err(message) => {
+                if part == 1 {
+                    eprintln!("{}", message);
+                }
+            }
+        }
+        rows.push(row);
+    }
+    rows
+}
+
+fn print_report(rows: &[Row]) {
+    let header = ["Day", "Part", "Input", "Answer", "Expected", "Result"];
+    let mut table: Vec<Vec<String>> = vec![header.map(|s| s.to_string()).to_vec()];
+    for row in rows.iter() {
+        table.push(vec![
+            row.day.to_string(),
+            row.part.to_string(),
+            row.input.clone(),
+            row.answer.clone(),
+            row.expected.clone(),
+            row.status.name().to_string(),
+        ]);
+    }
+    print_table(&table);
+}
+
+/// Re-run the solvers on every input with recorded answers, and on the default inputs.
+pub fn verify(args: VerifyArgs) -> Result<(), Failure> {
+    let answers = match &args.answers {
+        Some(filename) => Answers::load(filename, true)?,
+        None => Answers::load("answers.toml", false)?,
+    };
+    let mut rows: Vec<Row> = Vec::new();
+
+    for day in DAYS.iter() {
+        if args.day.is_some_and(|number| number != day.day()) {
+            continue;
+        }
+
+        let mut inputs: Vec<String> = Vec::new();
+        for input in answers.inputs(day.day()) {
+            inputs.push(input.to_string());
+        }
+        let default = default_input(&args.inputs, day.day());
+        if Path::new(&default).exists() && !inputs.contains(&default) {
+            inputs.push(default);
+        }
+
+        for input in inputs.iter() {
+            rows.extend(verify_input(*day, input, &answers));
+        }
+    }
+
+    print_report(&rows);
+
+    let count = |status: Status| rows.iter().filter(|row| row.status == status).count();
+    println!(
+        "\n{} passed, {} failed, {} unknown, {} errors",
+        count(Status::Pass),
+        count(Status::Fail),
+        count(Status::Unknown),
+        count(Status::Error)
+    );
+
+    if count(Status::Fail) + count(Status::Error) > 0 {
+        return Err(Failure::Verify);
+    }
+    Ok(())
+}
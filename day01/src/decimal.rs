@@ -0,0 +1,79 @@
+/// First code point of every run of decimal digits 0 to 9 in Unicode 14,
+/// i.e. the characters of general category Nd. Each run is exactly ten long.
+const ZEROS: [u32; 66] = [
+    0x30, 0x660, 0x6F0, 0x7C0, 0x966, 0x9E6, 0xA66, 0xAE6, 0xB66, 0xBE6, 0xC66, 0xCE6, 0xD66,
+    0xDE6, 0xE50, 0xED0, 0xF20, 0x1040, 0x1090, 0x17E0, 0x1810, 0x1946, 0x19D0, 0x1A80, 0x1A90,
+    0x1B50, 0x1BB0, 0x1C40, 0x1C50, 0xA620, 0xA8D0, 0xA900, 0xA9D0, 0xA9F0, 0xAA50, 0xABF0,
+    0xFF10, 0x104A0, 0x10D30, 0x11066, 0x110F0, 0x11136, 0x111D0, 0x112F0, 0x11450, 0x114D0,
+    0x11650, 0x116C0, 0x11730, 0x118E0, 0x11950, 0x11C50, 0x11D50, 0x11DA0, 0x16A60, 0x16AC0,
+    0x16B50, 0x1D7CE, 0x1D7D8, 0x1D7E2, 0x1D7EC, 0x1D7F6, 0x1E140, 0x1E2F0, 0x1E950, 0x1FBF0,
+];
+
+/// Value of a decimal digit of any script, e.g. 3 for '3', '٣' or '３'.
+/// Other numeric characters like '½', '²' or 'Ⅻ' are not decimal digits.
+pub fn decimal_digit(c: char) -> Option<u8> {
+    let code = c as u32;
+    let run = match ZEROS.binary_search(&code) {
+        Ok(run) => run,
+        Err(0) => return None,
+        Err(next) => next - 1,
+    };
+    let value = code - ZEROS[run];
+    if value < 10 {
+        Some(value as u8)
+    } else {
+        None
+    }
+}
+
+/// Length in bytes of the UTF-8 character starting with lead.
+pub fn utf8_len(lead: u8) -> usize {
+    match lead {
+        0xF0.. => 4,
+        0xE0.. => 3,
+        0xC0.. => 2,
+        _ => 1,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn digits_of_other_scripts() {
+        assert_eq!(decimal_digit('7'), Some(7));
+        assert_eq!(decimal_digit('٣'), Some(3));
+        assert_eq!(decimal_digit('۹'), Some(9));
+        assert_eq!(decimal_digit('०'), Some(0));
+        assert_eq!(decimal_digit('４'), Some(4));
+        assert_eq!(decimal_digit('𝟘'), Some(0));
+        assert_eq!(decimal_digit('𝟡'), Some(9));
+    }
+
+    #[test]
+    fn other_characters_are_not_digits() {
+        for c in ['a', '/', ':', 'ä', '½', '²', 'Ⅻ', '五', '\u{965}', '\u{970}'] {
+            assert_eq!(decimal_digit(c), None, "{:?}", c);
+        }
+    }
+
+    #[test]
+    fn every_run_has_ten_digits() {
+        for zero in ZEROS.iter() {
+            for value in 0..10 {
+                let c = char::from_u32(zero + value).unwrap();
+                assert!(c.is_numeric(), "{:?}", c);
+                assert_eq!(decimal_digit(c), Some(value as u8));
+            }
+        }
+    }
+
+    #[test]
+    fn utf8_len_of_lead_bytes() {
+        for c in ['a', 'ä', '٣', '４', '𝟘'] {
+            let mut buf = [0; 4];
+            assert_eq!(utf8_len(c.encode_utf8(&mut buf).as_bytes()[0]), c.len_utf8());
+        }
+    }
+}
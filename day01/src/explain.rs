@@ -0,0 +1,119 @@
+use crate::{get_tokens, join_first_and_last, DigitRules, Token};
+use std::fmt;
+
+/// How one calibration line is read: every digit and word found on it,
+/// and the value it adds to the sum.
+pub struct Explanation<'a> {
+    line: &'a str,
+    pub tokens: Vec<Token<'a>>,
+    pub value: u32,
+}
+
+impl<'a> Explanation<'a> {
+    pub fn new(line: &'a str, rules: DigitRules) -> Explanation<'a> {
+        let tokens: Vec<Token> = get_tokens(line, rules);
+        let digits: Vec<u8> = tokens.iter().map(|token| token.digit).collect();
+        Explanation {
+            line,
+            tokens,
+            value: join_first_and_last(&digits),
+        }
+    }
+
+    /// The line with every token in brackets, e.g. "[two][1][nine]".
+    /// Overlapping words share brackets, like "[eightwo]".
+    pub fn highlighted(&self) -> String {
+        // Byte ranges of the bracketed parts.
+        let mut spans: Vec<(usize, usize)> = Vec::new();
+        for token in self.tokens.iter() {
+            let end = token.start + token.text.len();
+            match spans.last_mut() {
+                Some(last) if token.start < last.1 => last.1 = last.1.max(end),
+                _ => spans.push((token.start, end)),
+            }
+        }
+
+        let mut res = String::new();
+        let mut pos: usize = 0;
+        for (start, end) in spans {
+            res.push_str(&self.line[pos..start]);
+            res.push('[');
+            res.push_str(&self.line[start..end]);
+            res.push(']');
+            pos = end;
+        }
+        res.push_str(&self.line[pos..]);
+        res
+    }
+
+    /// Column of a token in characters, starting from 1, like in parse errors.
+    pub fn column(&self, token: &Token) -> usize {
+        self.line[..token.start].chars().count() + 1
+    }
+}
+
+impl fmt::Display for Explanation<'_> {
+    /// The highlighted line followed by the tokens and the value:
+    ///
+    /// ```text
+    /// [two][1][nine]
+    ///     two = 2 at 1, 1 at 4, nine = 9 at 5
+    ///     first 2, last 9: 29
+    /// ```
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        writeln!(f, "{}", self.highlighted())?;
+
+        let (first, last) = match (self.tokens.first(), self.tokens.last()) {
+            (Some(first), Some(last)) => (first, last),
+            _ => return write!(f, "    no digits: 0"),
+        };
+
+        let mut found: Vec<String> = Vec::new();
+        for token in self.tokens.iter() {
+            // Words and digits of other scripts are shown with their value.
+            if token.word || !token.text.is_ascii() {
+                found.push(format!("{} = {} at {}", token.text, token.digit, self.column(token)));
+            } else {
+                found.push(format!("{} at {}", token.text, self.column(token)));
+            }
+        }
+        writeln!(f, "    {}", found.join(", "))?;
+        write!(f, "    first {}, last {}: {}", first.digit, last.digit, self.value)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Vocabulary;
+
+    #[test]
+    fn explains_words_and_digits() {
+        let english = Vocabulary::english();
+        let explanation = Explanation::new("two1nine", DigitRules::words(&english));
+        let expected = "[two][1][nine]\n    two = 2 at 1, 1 at 4, nine = 9 at 5\n    first 2, last 9: 29";
+        assert_eq!(explanation.to_string(), expected);
+    }
+
+    #[test]
+    fn overlapping_words_share_brackets() {
+        let english = Vocabulary::english();
+        let explanation = Explanation::new("xeightwo7", DigitRules::words(&english));
+        assert_eq!(explanation.highlighted(), "x[eightwo][7]");
+        assert_eq!(explanation.value, 87);
+    }
+
+    #[test]
+    fn columns_count_characters() {
+        let english = Vocabulary::english();
+        let explanation = Explanation::new("äöone", DigitRules::words(&english));
+        assert_eq!(explanation.highlighted(), "äö[one]");
+        assert_eq!(explanation.column(&explanation.tokens[0]), 3);
+    }
+
+    #[test]
+    fn line_without_digits() {
+        let explanation = Explanation::new("abc", DigitRules::DIGITS);
+        assert_eq!(explanation.to_string(), "abc\n    no digits: 0");
+    }
+}
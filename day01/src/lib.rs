@@ -0,0 +1,247 @@
+//! Day 1: Trebuchet?!
+
+mod decimal;
+mod explain;
+mod scanner;
+mod vocabulary;
+
+pub use explain::Explanation;
+pub use scanner::{stream_calibration_sums, CalibrationSums, Scanner};
+pub use vocabulary::Vocabulary;
+
+use aoc_common::{Answer, Lines, Normalize, ParseError, Solution};
+use std::option::Option;
+
+/// Digit spelled out at the start of chars, with the length of the word.
+fn string_to_num(chars: &[u8], vocabulary: &Vocabulary) -> Option<(usize, u8)> {
+    vocabulary.match_start(chars).map(|(word, digit)| (word.len(), digit))
+}
+
+/// What is read as a digit on a calibration line.
+#[derive(Clone, Copy, Debug, Default)]
+pub struct DigitRules<'a> {
+    /// Spelled out digits like "one", or None for digits only.
+    pub vocabulary: Option<&'a Vocabulary>,
+    /// Read decimal digits of every script, like '٣' or '３', not only 0 to 9.
+    pub unicode: bool,
+}
+
+impl<'a> DigitRules<'a> {
+    /// Rules of part 1: the digits 0 to 9.
+    pub const DIGITS: DigitRules<'static> = DigitRules {
+        vocabulary: None,
+        unicode: false,
+    };
+
+    /// Rules of part 2: the digits 0 to 9 and the words of vocabulary.
+    pub fn words(vocabulary: &'a Vocabulary) -> DigitRules<'a> {
+        DigitRules {
+            vocabulary: Some(vocabulary),
+            unicode: false,
+        }
+    }
+}
+
+/// Digit starting at byte i of str, with its length in bytes.
+/// With unicode, digits of every script are read.
+fn digit_at(str: &str, i: usize, unicode: bool) -> Option<(usize, u8)> {
+    let b: u8 = str.as_bytes()[i];
+    if b.is_ascii_digit() {
+        Some((1, b - b'0'))
+    } else if unicode && b >= 0xC0 {
+        let c: char = str[i..].chars().next()?;
+        decimal::decimal_digit(c).map(|digit| (c.len_utf8(), digit))
+    } else {
+        None
+    }
+}
+
+/// A digit or a spelled out digit on a line.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Token<'a> {
+    /// Byte offset on the line.
+    pub start: usize,
+    /// The digit or the word as it is on the line.
+    pub text: &'a str,
+    pub digit: u8,
+    /// Spelled out rather than a digit.
+    pub word: bool,
+}
+
+/// Get list of digits and words in a string in the order they start.
+/// Words may overlap, like "two" and "one" in "twone".
+fn get_tokens<'a>(str: &'a str, rules: DigitRules) -> Vec<Token<'a>> {
+    let bytes: &[u8] = str.as_bytes();
+    let mut retval: Vec<Token> = Vec::new();
+
+    // Going byte by byte is fine for UTF-8: a word can only match where a character starts,
+    // and only the first byte of a multibyte character is 0xC0 or more.
+    for i in 0..bytes.len() {
+        let (len, digit, word) = if let Some((len, digit)) = digit_at(str, i, rules.unicode) {
+            (len, digit, false)
+        } else if let Some((len, digit)) = rules.vocabulary.and_then(|v| string_to_num(&bytes[i..], v)) {
+            (len, digit, true)
+        } else {
+            continue;
+        };
+        retval.push(Token {
+            start: i,
+            text: &str[i..i + len],
+            digit,
+            word,
+        });
+    }
+    retval
+}
+
+/// Get list of digits in a string in the order they appear.
+fn get_digits(str: &str, rules: DigitRules) -> Vec<u8> {
+    get_tokens(str, rules).iter().map(|token| token.digit).collect()
+}
+
+fn join_first_and_last(values: &[u8]) -> u32 {
+    if values.is_empty() {
+        return 0;
+    }
+    (values[0] as u32) * 10 + values[values.len() - 1] as u32
+}
+
+/// Sum of the calibration values of all lines.
+pub fn calibration_sum(input: &[String], rules: DigitRules) -> u32 {
+    let mut sum: u32 = 0;
+    for line in input {
+        let digits: Vec<u8> = get_digits(line, rules);
+        sum += join_first_and_last(&digits);
+    }
+    sum
+}
+
+pub struct Day01;
+
+impl Solution for Day01 {
+    const DAY: u8 = 1;
+    /// Calibration lines are used exactly as they are.
+    const NORMALIZE: Normalize = Normalize::RAW;
+
+    type Input = Vec<String>;
+
+    fn parse(input: &Lines) -> Result<Vec<String>, ParseError> {
+        Ok(input.to_vec())
+    }
+
+    /// Sum of the calibration values, digits only.
+    fn part1(input: &Vec<String>) -> Answer {
+        calibration_sum(input, DigitRules::DIGITS).into()
+    }
+
+    /// Sum of the calibration values, with spelled out digits included.
+    fn part2(input: &Vec<String>) -> Answer {
+        let english = Vocabulary::english();
+        calibration_sum(input, DigitRules::words(&english)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse_example;
+
+    const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+    const EXAMPLE2: &str = include_str!("../fixtures/example2.txt");
+    const UTF8: &str = include_str!("../fixtures/utf8.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = parse_example::<Day01>(EXAMPLE1);
+        assert_eq!(Day01::part1(&input), Answer::Number(142));
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = parse_example::<Day01>(EXAMPLE2);
+        assert_eq!(Day01::part2(&input), Answer::Number(281));
+    }
+
+    #[test]
+    fn string_to_num_reads_word_at_start() {
+        let english = Vocabulary::english();
+        assert_eq!(string_to_num(b"one", &english), Some((3, 1)));
+        assert_eq!(string_to_num(b"sevenine", &english), Some((5, 7)));
+        assert_eq!(string_to_num(b"nineight", &english), Some((4, 9)));
+        assert_eq!(string_to_num(b"xone", &english), None);
+        assert_eq!(string_to_num(b"on", &english), None);
+    }
+
+    #[test]
+    fn get_digits_keeps_overlapping_words() {
+        let english = Vocabulary::english();
+        assert_eq!(get_digits("two1nine", DigitRules::words(&english)), vec![2, 1, 9]);
+        assert_eq!(get_digits("eightwothree", DigitRules::words(&english)), vec![8, 2, 3]);
+        assert_eq!(get_digits("zoneight234", DigitRules::words(&english)), vec![1, 8, 2, 3, 4]);
+    }
+
+    #[test]
+    fn get_digits_without_words() {
+        assert_eq!(get_digits("two1nine", DigitRules::DIGITS), vec![1]);
+        assert_eq!(get_digits("zoneight234", DigitRules::DIGITS), vec![2, 3, 4]);
+        assert!(get_digits("eightwothree", DigitRules::DIGITS).is_empty());
+    }
+
+    #[test]
+    fn get_digits_in_other_languages() {
+        let finnish = Vocabulary::by_name("finnish").unwrap();
+        assert_eq!(get_digits("neljäviisinolla", DigitRules::words(&finnish)), vec![4, 5, 0]);
+
+        let german = Vocabulary::by_name("german").unwrap();
+        assert_eq!(get_digits("xfünfzweinsnull", DigitRules::words(&german)), vec![5, 2, 1, 0]);
+    }
+
+    #[test]
+    fn multibyte_characters_do_not_shift_words() {
+        let english = Vocabulary::english();
+        assert_eq!(get_digits("ääone2", DigitRules::words(&english)), vec![1, 2]);
+        assert_eq!(get_digits("日本two語3", DigitRules::words(&english)), vec![2, 3]);
+        assert_eq!(get_digits("😀eightwo😀", DigitRules::words(&english)), vec![8, 2]);
+    }
+
+    #[test]
+    fn unicode_digits_only_with_the_flag() {
+        let unicode = DigitRules {
+            vocabulary: None,
+            unicode: true,
+        };
+        assert_eq!(get_digits("٣abc٧", unicode), vec![3, 7]);
+        assert_eq!(get_digits("ｎｉｎｅ３x9", unicode), vec![3, 9]);
+        assert!(get_digits("Ⅻ½²", unicode).is_empty());
+        assert!(get_digits("٣abc٧", DigitRules::DIGITS).is_empty());
+    }
+
+    #[test]
+    fn words_starting_with_multibyte_characters() {
+        let lines = Lines::new("greek", "ένα 1\nδύο 2", Normalize::TRIMMED);
+        let greek = Vocabulary::parse(&lines).unwrap();
+        let rules = DigitRules {
+            vocabulary: Some(&greek),
+            unicode: true,
+        };
+        assert_eq!(get_digits("xδύο٣ένα", rules), vec![2, 3, 1]);
+    }
+
+    #[test]
+    fn utf8_corpus() {
+        let input = parse_example::<Day01>(UTF8);
+        let english = Vocabulary::english();
+        let unicode = DigitRules {
+            vocabulary: None,
+            unicode: true,
+        };
+        let words_unicode = DigitRules {
+            vocabulary: Some(&english),
+            unicode: true,
+        };
+        assert_eq!(Day01::part1(&input), Answer::Number(286));
+        assert_eq!(Day01::part2(&input), Answer::Number(568));
+        assert_eq!(calibration_sum(&input, unicode), 415);
+        assert_eq!(calibration_sum(&input, words_unicode), 584);
+    }
+}
@@ -0,0 +1,318 @@
+use crate::decimal::{decimal_digit, utf8_len};
+use crate::DigitRules;
+use std::collections::VecDeque;
+use std::io::{self, BufRead, BufReader, Read};
+
+/// Calibration sums of both parts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct CalibrationSums {
+    /// Digits only.
+    pub digits: u64,
+    /// Spelled out digits included.
+    pub words: u64,
+}
+
+/// First and last digit seen on the current line.
+#[derive(Default)]
+struct Calibration {
+    first: Option<u8>,
+    last: Option<u8>,
+}
+
+impl Calibration {
+    fn push(&mut self, digit: u8) {
+        if self.first.is_none() {
+            self.first = Some(digit);
+        }
+        self.last = Some(digit);
+    }
+
+    /// Value of the line, clearing it for the next one.
+    fn take(&mut self) -> u64 {
+        let value = match (self.first, self.last) {
+            (Some(first), Some(last)) => first as u64 * 10 + last as u64,
+            _ => 0,
+        };
+        *self = Calibration::default();
+        value
+    }
+}
+
+/// Node of a trie of the vocabulary.
+#[derive(Default)]
+struct Node {
+    children: Vec<(u8, usize)>,
+    /// Digit of the word ending here.
+    digit: Option<u8>,
+}
+
+/// Scans calibration lines byte by byte, keeping only as many bytes as the longest word.
+/// Every position is looked at once as the start of a token, so overlapping words like
+/// "twone" give both digits, just like `get_digits`.
+pub struct Scanner {
+    trie: Vec<Node>,
+    /// Upcoming bytes of the line, starting from the next position to look at.
+    window: VecDeque<u8>,
+    /// Length of the longest word, at least 1, or 4 to decode any character with unicode digits.
+    lookahead: usize,
+    unicode: bool,
+    digits: Calibration,
+    words: Calibration,
+    sums: CalibrationSums,
+}
+
+impl Scanner {
+    /// Without a vocabulary only digits are read, and the two sums are the same.
+    pub fn new(rules: DigitRules) -> Scanner {
+        let mut trie: Vec<Node> = vec![Node::default()];
+        let mut lookahead: usize = if rules.unicode { 4 } else { 1 };
+
+        for (word, digit) in rules.vocabulary.into_iter().flat_map(|v| v.words()) {
+            let mut node: usize = 0;
+            for b in word.bytes() {
+                node = match trie[node].children.iter().find(|(c, _)| *c == b) {
+                    Some((_, next)) => *next,
+                    None => {
+                        trie.push(Node::default());
+                        let next = trie.len() - 1;
+                        trie[node].children.push((b, next));
+                        next
+                    }
+                };
+            }
+            trie[node].digit = Some(digit);
+            lookahead = lookahead.max(word.len());
+        }
+
+        Scanner {
+            trie,
+            window: VecDeque::with_capacity(lookahead),
+            lookahead,
+            unicode: rules.unicode,
+            digits: Calibration::default(),
+            words: Calibration::default(),
+            sums: CalibrationSums::default(),
+        }
+    }
+
+    /// Digit of the longest word at the start of the window.
+    fn match_word(&self) -> Option<u8> {
+        let mut node: usize = 0;
+        let mut res: Option<u8> = None;
+
+        for b in self.window.iter() {
+            node = match self.trie[node].children.iter().find(|(c, _)| c == b) {
+                Some((_, next)) => *next,
+                None => break,
+            };
+            if self.trie[node].digit.is_some() {
+                res = self.trie[node].digit;
+            }
+        }
+        res
+    }
+
+    /// Digit at the start of the window, whose first byte is b.
+    /// With unicode digits of every script are read, and invalid UTF-8 is not a digit.
+    fn match_digit(&self, b: u8) -> Option<u8> {
+        if b.is_ascii_digit() {
+            return Some(b - b'0');
+        }
+        if !self.unicode || b < 0xC0 {
+            return None;
+        }
+
+        let len: usize = utf8_len(b);
+        let mut buf: [u8; 4] = [0; 4];
+        for (i, b) in self.window.iter().take(len).enumerate() {
+            buf[i] = *b;
+        }
+        let c: char = std::str::from_utf8(&buf[..len]).ok()?.chars().next()?;
+        decimal_digit(c)
+    }
+
+    /// Look at the first byte of the window as the start of a token, and drop it.
+    fn step(&mut self) {
+        let b: u8 = match self.window.front() {
+            Some(b) => *b,
+            None => return,
+        };
+
+        if let Some(digit) = self.match_digit(b) {
+            self.digits.push(digit);
+            self.words.push(digit);
+        } else if let Some(digit) = self.match_word() {
+            self.words.push(digit);
+        }
+        self.window.pop_front();
+    }
+
+    fn end_line(&mut self) {
+        while !self.window.is_empty() {
+            self.step();
+        }
+        self.sums.digits += self.digits.take();
+        self.sums.words += self.words.take();
+    }
+
+    pub fn push(&mut self, b: u8) {
+        if b == b'\n' {
+            self.end_line();
+            return;
+        }
+
+        self.window.push_back(b);
+        if self.window.len() == self.lookahead {
+            self.step();
+        }
+    }
+
+    /// Sums of all the lines, including a last one without a newline.
+    pub fn finish(mut self) -> CalibrationSums {
+        self.end_line();
+        self.sums
+    }
+}
+
+/// Calibration sums of everything read from reader, in constant memory.
+/// The digit sum uses rules without the vocabulary.
+pub fn stream_calibration_sums(reader: impl Read, rules: DigitRules) -> io::Result<CalibrationSums> {
+    let mut reader = BufReader::new(reader);
+    let mut scanner = Scanner::new(rules);
+
+    loop {
+        let buf: &[u8] = reader.fill_buf()?;
+        if buf.is_empty() {
+            break;
+        }
+        for b in buf.iter() {
+            scanner.push(*b);
+        }
+        let len = buf.len();
+        reader.consume(len);
+    }
+    Ok(scanner.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{calibration_sum, Vocabulary};
+
+    /// Sums the way the in-memory solver computes them.
+    fn in_memory(text: &str, rules: DigitRules) -> CalibrationSums {
+        let lines: Vec<String> = text.lines().map(|line| line.to_string()).collect();
+        let digits = DigitRules {
+            vocabulary: None,
+            ..rules
+        };
+        CalibrationSums {
+            digits: calibration_sum(&lines, digits) as u64,
+            words: calibration_sum(&lines, rules) as u64,
+        }
+    }
+
+    /// Reader handing out one byte at a time, to split tokens between reads.
+    struct Trickle<'a>(&'a [u8]);
+
+    impl Read for Trickle<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            if self.0.is_empty() || buf.is_empty() {
+                return Ok(0);
+            }
+            buf[0] = self.0[0];
+            self.0 = &self.0[1..];
+            Ok(1)
+        }
+    }
+
+    /// Endless lines of "twone3eightwo", cut off after len bytes.
+    struct Repeat {
+        len: u64,
+        pos: u64,
+    }
+
+    impl Read for Repeat {
+        fn read(&mut self, buf: &mut [u8]) -> io::Result<usize> {
+            const LINE: &[u8] = b"twone3eightwo\n";
+            let mut n: usize = 0;
+            while n < buf.len() && self.pos < self.len {
+                buf[n] = LINE[(self.pos % LINE.len() as u64) as usize];
+                n += 1;
+                self.pos += 1;
+            }
+            Ok(n)
+        }
+    }
+
+    #[test]
+    fn overlapping_words() {
+        let english = Vocabulary::english();
+        let sums = stream_calibration_sums("twone\neightwo\nxtwone3four".as_bytes(), DigitRules::words(&english)).unwrap();
+        assert_eq!(sums.words, 21 + 82 + 24);
+        assert_eq!(sums.digits, 33);
+    }
+
+    #[test]
+    fn same_as_in_memory_solver() {
+        let english = Vocabulary::english();
+        let text = concat!(
+            include_str!("../fixtures/example1.txt"),
+            include_str!("../fixtures/example2.txt"),
+            "\nsevenine\nno digits\n\n9\nthreeeight\r\noneight"
+        );
+
+        let expected = in_memory(text, DigitRules::words(&english));
+        assert_eq!(stream_calibration_sums(text.as_bytes(), DigitRules::words(&english)).unwrap(), expected);
+        assert_eq!(stream_calibration_sums(Trickle(text.as_bytes()), DigitRules::words(&english)).unwrap(), expected);
+    }
+
+    #[test]
+    fn longest_word_at_a_position() {
+        let lines = aoc_common::Lines::new("roman", "I 1\nII 2\nIV 4\nV 5", aoc_common::Normalize::TRIMMED);
+        let roman = Vocabulary::parse(&lines).unwrap();
+        let text = "xIVx\nIIx\nVII";
+
+        let expected = in_memory(text, DigitRules::words(&roman));
+        assert_eq!(stream_calibration_sums(text.as_bytes(), DigitRules::words(&roman)).unwrap(), expected);
+    }
+
+    #[test]
+    fn large_input_in_constant_memory() {
+        // 2 MB of lines, each worth 33 with digits only and 22 with words: 2, 1, 3, 8, 2.
+        let lines: u64 = 2_000_000 / 14;
+        let reader = Repeat {
+            len: lines * 14,
+            pos: 0,
+        };
+        let sums = stream_calibration_sums(reader, DigitRules::words(&Vocabulary::english())).unwrap();
+        assert_eq!(sums, CalibrationSums { digits: 33 * lines, words: 22 * lines });
+    }
+
+    #[test]
+    fn multibyte_characters_like_in_memory_solver() {
+        let english = Vocabulary::english();
+        let text = include_str!("../fixtures/utf8.txt");
+
+        for unicode in [false, true] {
+            let rules = DigitRules {
+                vocabulary: Some(&english),
+                unicode,
+            };
+            let expected = in_memory(text, rules);
+            assert_eq!(stream_calibration_sums(text.as_bytes(), rules).unwrap(), expected);
+            assert_eq!(stream_calibration_sums(Trickle(text.as_bytes()), rules).unwrap(), expected);
+        }
+    }
+
+    #[test]
+    fn invalid_utf8_is_not_a_digit() {
+        let rules = DigitRules {
+            vocabulary: None,
+            unicode: true,
+        };
+        // A cut off '٣' and a lone continuation byte.
+        let text: &[u8] = b"\xd9\n\xa3\x97\n1\xd9";
+        assert_eq!(stream_calibration_sums(text, rules).unwrap().digits, 11);
+    }
+}
@@ -0,0 +1,149 @@
+use aoc_common::{parse_inputfile, Lines, Normalize, ParseError};
+use std::cmp::Reverse;
+
+/// Words that are read as digits, e.g. "one" for 1.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Vocabulary {
+    /// Longest words first, so the longest match at a position wins.
+    words: Vec<(String, u8)>,
+}
+
+/// Names of the built in vocabularies.
+pub const NAMES: [&str; 4] = ["english", "english-zero", "finnish", "german"];
+
+const ENGLISH: [&str; 10] = [
+    "zero", "one", "two", "three", "four", "five", "six", "seven", "eight", "nine",
+];
+const FINNISH: [&str; 10] = [
+    "nolla", "yksi", "kaksi", "kolme", "neljä", "viisi", "kuusi", "seitsemän", "kahdeksan", "yhdeksän",
+];
+const GERMAN: [&str; 10] = [
+    "null", "eins", "zwei", "drei", "vier", "fünf", "sechs", "sieben", "acht", "neun",
+];
+
+impl Vocabulary {
+    pub fn new(words: Vec<(String, u8)>) -> Vocabulary {
+        let mut words = words;
+        words.sort_by_key(|(word, _)| Reverse(word.len()));
+        Vocabulary { words }
+    }
+
+    /// Words for 0 to 9, starting from the word for from.
+    fn from_table(table: &[&str; 10], from: u8) -> Vocabulary {
+        let mut words: Vec<(String, u8)> = Vec::new();
+        for (digit, word) in table.iter().enumerate().skip(from as usize) {
+            words.push((word.to_string(), digit as u8));
+        }
+        Vocabulary::new(words)
+    }
+
+    /// "one" to "nine", as in the puzzle. "zero" is not a digit there.
+    pub fn english() -> Vocabulary {
+        Vocabulary::from_table(&ENGLISH, 1)
+    }
+
+    /// Built in vocabulary by name, see `NAMES`.
+    pub fn by_name(name: &str) -> Option<Vocabulary> {
+        match name {
+            "english" => Some(Vocabulary::english()),
+            "english-zero" => Some(Vocabulary::from_table(&ENGLISH, 0)),
+            "finnish" => Some(Vocabulary::from_table(&FINNISH, 0)),
+            "german" => Some(Vocabulary::from_table(&GERMAN, 0)),
+            _ => None,
+        }
+    }
+
+    /// Parse a vocabulary file with a word and its digit on every line:
+    ///
+    /// ```text
+    /// # Roman numerals
+    /// I 1
+    /// II 2
+    /// ```
+    pub fn parse(lines: &Lines) -> Result<Vocabulary, ParseError> {
+        let mut words: Vec<(String, u8)> = Vec::new();
+
+        for (i, line) in lines.iter().enumerate() {
+            if line.starts_with('#') {
+                continue;
+            }
+            let mut parts = line.split_whitespace();
+            let (word, digit) = match (parts.next(), parts.next(), parts.next()) {
+                (Some(word), Some(digit), None) => (word, digit),
+                _ => return Err(lines.error(i, line, "expected a word and its digit")),
+            };
+
+            let digit: u8 = lines.parse(i, digit)?;
+            if digit > 9 {
+                return Err(lines.error(i, line, format!("{} is not a digit", digit)));
+            }
+            if words.iter().any(|(w, _)| w == word) {
+                return Err(lines.error(i, word, format!("'{}' is listed twice", word)));
+            }
+            words.push((word.to_string(), digit));
+        }
+
+        if words.is_empty() {
+            return Err(lines.missing("expected at least one word"));
+        }
+        Ok(Vocabulary::new(words))
+    }
+
+    /// Built in vocabulary by name, or else read from a file.
+    pub fn load(name: &str) -> Result<Vocabulary, String> {
+        if let Some(vocabulary) = Vocabulary::by_name(name) {
+            return Ok(vocabulary);
+        }
+        let lines = parse_inputfile(name, Normalize::TRIMMED).map_err(|err| {
+            format!("{} (built in vocabularies are {})", err, NAMES.join(", "))
+        })?;
+        Vocabulary::parse(&lines).map_err(|err| err.to_string())
+    }
+
+    /// Every word with its digit, longest first.
+    pub fn words(&self) -> impl Iterator<Item = (&str, u8)> {
+        self.words.iter().map(|(word, digit)| (word.as_str(), *digit))
+    }
+
+    /// Word at the start of bytes, with its digit. The longest word wins.
+    pub fn match_start(&self, bytes: &[u8]) -> Option<(&str, u8)> {
+        self.words
+            .iter()
+            .find(|(word, _)| bytes.starts_with(word.as_bytes()))
+            .map(|(word, digit)| (word.as_str(), *digit))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn built_in_vocabularies() {
+        for name in NAMES {
+            assert!(Vocabulary::by_name(name).is_some());
+        }
+        let finnish = Vocabulary::by_name("finnish").unwrap();
+        assert_eq!(finnish.match_start("neljä".as_bytes()), Some(("neljä", 4)));
+        assert_eq!(finnish.match_start(b"nolla"), Some(("nolla", 0)));
+        assert_eq!(Vocabulary::english().match_start(b"zero"), None);
+    }
+
+    #[test]
+    fn longest_word_wins() {
+        let lines = Lines::new("roman", "# Roman numerals\nI 1\nII 2\nIII 3\nIV 4\nV 5", Normalize::TRIMMED);
+        let roman = Vocabulary::parse(&lines).unwrap();
+        assert_eq!(roman.match_start(b"IIV"), Some(("II", 2)));
+        assert_eq!(roman.match_start(b"IV"), Some(("IV", 4)));
+        assert_eq!(roman.match_start(b"X"), None);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let lines = Lines::new("bad", "one 1\ntwo 12", Normalize::TRIMMED);
+        assert_eq!(Vocabulary::parse(&lines).err().unwrap().line, 2);
+
+        let lines = Lines::new("bad", "one 1\none 2", Normalize::TRIMMED);
+        assert_eq!(Vocabulary::parse(&lines).err().unwrap().line, 2);
+    }
+}
@@ -0,0 +1,252 @@
+//! Maximum likelihood estimate of the contents of the bag a game was played with.
+//!
+//! Every set is a draw without replacement from the bag, so the chance of a set is
+//! multivariate hypergeometric:
+//!
+//! ```text
+//! P(set | bag) = C(n_red, k_red) * C(n_green, k_green) * ... / C(n, k)
+//! ```
+//!
+//! with n cubes in the bag and k drawn. The cubes are put back between sets, so the
+//! likelihood of a bag is the product over the sets. For a fixed total n the log-likelihood
+//! is a sum of one term per color, and the best split of every total among the colors is
+//! found by dynamic programming, one color at a time.
+//!
+//! The likelihood does not always have a maximum: a game that only ever draws cubes of one
+//! color per set is more likely the bigger the bag. Counts are therefore searched up to a
+//! limit, and estimates that reach it are only lower bounds.
+
+use crate::{Color, Game, Palette};
+
+/// Drop in log-likelihood at the ends of a 95% profile likelihood interval,
+/// half the 95% quantile of the chi-squared distribution with one degree of freedom.
+const INTERVAL_DROP: f64 = 3.841 / 2.0;
+
+/// Estimated number of cubes of one color.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ColorEstimate {
+    pub color: Color,
+    /// Most likely count.
+    pub count: u32,
+    /// Ends of the 95% confidence interval of the count.
+    pub low: u32,
+    pub high: u32,
+}
+
+/// Most likely bag of one game.
+#[derive(Clone, Debug, PartialEq)]
+pub struct Estimate {
+    pub index: u32,
+    /// One estimate per color of the palette.
+    pub colors: Vec<ColorEstimate>,
+    /// Log-likelihood of the most likely bag.
+    pub log_likelihood: f64,
+    /// Largest count searched. An estimate or interval reaching it has no upper bound.
+    pub max_count: u32,
+}
+
+/// ln(n!) for n from 0 to max.
+fn log_factorials(max: usize) -> Vec<f64> {
+    let mut res: Vec<f64> = vec![0.0];
+    for n in 1..=max {
+        res.push(res[n - 1] + (n as f64).ln());
+    }
+    res
+}
+
+/// ln C(n, k), minus infinity when k > n.
+fn log_choose(log_factorials: &[f64], n: usize, k: usize) -> f64 {
+    if k > n {
+        return f64::NEG_INFINITY;
+    }
+    log_factorials[n] - log_factorials[k] - log_factorials[n - k]
+}
+
+/// Best split of every total among colors, whose log-likelihood terms by count are given.
+/// Returns the best sum of terms for every total, and the count of every color in it.
+fn allocate(terms: &[&Vec<f64>], totals: usize) -> (Vec<f64>, Vec<Vec<usize>>) {
+    let mut best: Vec<f64> = vec![f64::NEG_INFINITY; totals + 1];
+    best[0] = 0.0;
+    let mut choices: Vec<Vec<usize>> = Vec::new();
+
+    for term in terms.iter() {
+        let mut next: Vec<f64> = vec![f64::NEG_INFINITY; totals + 1];
+        let mut choice: Vec<usize> = vec![0; totals + 1];
+        for (total, before) in best.iter().enumerate() {
+            if *before == f64::NEG_INFINITY {
+                continue;
+            }
+            for (count, value) in term.iter().enumerate() {
+                let sum = before + value;
+                // Ties go to the smaller count, which is tried first.
+                if total + count <= totals && sum > next[total + count] {
+                    next[total + count] = sum;
+                    choice[total + count] = count;
+                }
+            }
+        }
+        best = next;
+        choices.push(choice);
+    }
+    (best, choices)
+}
+
+/// Estimate the bag of game, searching every count of the colors of palette up to max_count.
+/// The search always goes at least up to the most cubes of one color drawn at once.
+pub fn estimate_bag(game: &Game, palette: &Palette, max_count: u32) -> Estimate {
+    let colors: Vec<Color> = palette.colors().collect();
+    let drawn_max: u32 = game.cube_sets.iter().flat_map(|set| set.values()).copied().max().unwrap_or(0);
+    let max_count = max_count.max(drawn_max) as usize;
+    let totals = max_count * colors.len();
+    let log_factorials = log_factorials(totals);
+
+    // Log-likelihood terms of each color by its count: the sum of ln C(count, drawn) over the sets.
+    let mut terms: Vec<Vec<f64>> = Vec::new();
+    for color in colors.iter() {
+        let mut term: Vec<f64> = vec![0.0; max_count + 1];
+        for set in game.cube_sets.iter() {
+            let drawn = *set.get(color).unwrap_or(&0) as usize;
+            for (count, value) in term.iter_mut().enumerate() {
+                *value += log_choose(&log_factorials, count, drawn);
+            }
+        }
+        terms.push(term);
+    }
+
+    // The term of the total: minus the sum of ln C(total, drawn) over the sets.
+    // A bag smaller than a set is impossible, not infinitely likely.
+    let mut total_term: Vec<f64> = vec![0.0; totals + 1];
+    for set in game.cube_sets.iter() {
+        let drawn = set.values().sum::<u32>() as usize;
+        for (total, value) in total_term.iter_mut().enumerate() {
+            if total < drawn {
+                *value = f64::NEG_INFINITY;
+            } else {
+                *value -= log_choose(&log_factorials, total, drawn);
+            }
+        }
+    }
+
+    let all: Vec<&Vec<f64>> = terms.iter().collect();
+    let (best, choices) = allocate(&all, totals);
+    let mut best_total: usize = 0;
+    for total in 0..=totals {
+        if best[total] + total_term[total] > best[best_total] + total_term[best_total] {
+            best_total = total;
+        }
+    }
+    let log_likelihood = best[best_total] + total_term[best_total];
+
+    // Counts of the best bag, from the last color back.
+    let mut counts: Vec<usize> = vec![0; colors.len()];
+    let mut total = best_total;
+    for j in (0..colors.len()).rev() {
+        counts[j] = choices[j][total];
+        total -= counts[j];
+    }
+
+    let mut res: Vec<ColorEstimate> = Vec::new();
+    for (j, color) in colors.iter().enumerate() {
+        // Profile likelihood of every count: the best bag with that many cubes of this color.
+        let others: Vec<&Vec<f64>> = terms.iter().enumerate().filter(|(k, _)| *k != j).map(|(_, t)| t).collect();
+        let (best_others, _) = allocate(&others, totals);
+        let mut inside: Vec<usize> = Vec::new();
+        for count in 0..=max_count {
+            let mut profile = f64::NEG_INFINITY;
+            for rest in 0..=totals - count {
+                profile = profile.max(best_others[rest] + terms[j][count] + total_term[rest + count]);
+            }
+            if profile >= log_likelihood - INTERVAL_DROP {
+                inside.push(count);
+            }
+        }
+
+        res.push(ColorEstimate {
+            color: *color,
+            count: counts[j] as u32,
+            low: inside[0] as u32,
+            high: inside[inside.len() - 1] as u32,
+        });
+    }
+
+    Estimate {
+        index: game.index,
+        colors: res,
+        log_likelihood,
+        max_count: max_count as u32,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_games, Day02};
+    use aoc_common::{Lines, Solution};
+
+    fn game(line: &str) -> Game {
+        let lines = Lines::new("test", line, Day02::NORMALIZE);
+        parse_games(&lines, &Palette::default()).unwrap().remove(0)
+    }
+
+    /// Log-likelihood of a bag straight from the hypergeometric probabilities.
+    fn log_likelihood(game: &Game, bag: &[usize]) -> f64 {
+        let log_factorials = log_factorials(100);
+        let total: usize = bag.iter().sum();
+        let mut res = 0.0;
+        for set in game.cube_sets.iter() {
+            let mut drawn: usize = 0;
+            for (j, color) in Palette::default().colors().enumerate() {
+                let k = *set.get(&color).unwrap_or(&0) as usize;
+                res += log_choose(&log_factorials, bag[j], k);
+                drawn += k;
+            }
+            res -= log_choose(&log_factorials, total, drawn);
+        }
+        res
+    }
+
+    #[test]
+    fn one_set_is_its_own_bag() {
+        let estimate = estimate_bag(&game("Game 7: 3 red, 1 blue"), &Palette::default(), 20);
+        let counts: Vec<u32> = estimate.colors.iter().map(|c| c.count).collect();
+        assert_eq!(estimate.index, 7);
+        assert_eq!(counts, vec![3, 0, 1]);
+        assert!(estimate.log_likelihood.abs() < 1e-9);
+    }
+
+    #[test]
+    fn same_as_searching_every_bag() {
+        let game = game("Game 1: 3 blue, 4 red; 1 red, 2 green, 6 blue; 2 green");
+        let max_count: usize = 12;
+
+        let mut best: (f64, Vec<usize>) = (f64::NEG_INFINITY, Vec::new());
+        for red in 0..=max_count {
+            for green in 0..=max_count {
+                for blue in 0..=max_count {
+                    let value = log_likelihood(&game, &[red, green, blue]);
+                    if value > best.0 + 1e-12 {
+                        best = (value, vec![red, green, blue]);
+                    }
+                }
+            }
+        }
+
+        let estimate = estimate_bag(&game, &Palette::default(), max_count as u32);
+        let counts: Vec<usize> = estimate.colors.iter().map(|c| c.count as usize).collect();
+        assert!((estimate.log_likelihood - best.0).abs() < 1e-9);
+        assert!((log_likelihood(&game, &counts) - best.0).abs() < 1e-9);
+        for c in estimate.colors.iter() {
+            assert!(c.low <= c.count && c.count <= c.high);
+        }
+        // Never fewer cubes than were seen at once.
+        assert!(estimate.colors[0].low >= 4 && estimate.colors[2].low >= 6);
+    }
+
+    #[test]
+    fn unbounded_when_sets_never_mix_colors() {
+        let estimate = estimate_bag(&game("Game 1: 2 red; 2 blue"), &Palette::default(), 30);
+        let counts: Vec<u32> = estimate.colors.iter().map(|c| c.count).collect();
+        assert_eq!(counts, vec![30, 0, 30]);
+        assert_eq!(estimate.colors[0].high, estimate.max_count);
+    }
+}
@@ -0,0 +1,475 @@
+//! Day 2: Cube Conundrum
+
+mod estimate;
+mod palette;
+mod validate;
+
+pub use estimate::{estimate_bag, ColorEstimate, Estimate};
+pub use palette::{Color, Palette};
+pub use validate::validate_games;
+
+use aoc_common::{Answer, Lines, Normalize, ParseError, Solution};
+use std::collections::HashMap;
+use std::option::Option;
+
+pub struct Game {
+    cube_sets: Vec<HashMap<Color, u32>>,
+    index: u32,
+}
+
+/// Cubes in a bag, by color. Colors that are not listed are not in the bag.
+pub type Bag = HashMap<Color, u32>;
+
+/// Get index of the game on line i
+fn parse_index(lines: &Lines, i: usize) -> Result<u32, ParseError> {
+    let input: &str = &lines[i];
+    if !input.starts_with("Game ") {
+        return Err(lines.error(i, input, "expected a line starting with 'Game '"));
+    }
+
+    match input.find(':') {
+        Some(end) => lines.parse(i, &input[5..end]),
+        None => Err(lines.error(i, &input[input.len()..], "expected ':' after the game index")),
+    }
+}
+
+fn get_next_or_end(input: &str, limiter: char) -> usize {
+    let limiter_index: Option<usize> = input.find(limiter);
+
+    if limiter_index.is_none() {
+        return input.len();
+    }
+
+    limiter_index.unwrap()
+}
+
+/// Input should be in format 'count color', a slice of line i.
+/// The color is returned as a slice of input.
+fn parse_color_count<'a>(lines: &Lines, i: usize, input: &'a str) -> Result<(&'a str, u32), ParseError> {
+    let mut parts = input.split(' ');
+
+    let count: u32 = lines.parse(i, parts.next().unwrap_or(input))?;
+    let color = match parts.next() {
+        Some(color) if !color.is_empty() => color,
+        _ => return Err(lines.error(i, &input[input.len()..], "expected a color after the count")),
+    };
+    if let Some(extra) = parts.next() {
+        return Err(lines.error(i, extra, "expected ',' or ';' after the color"));
+    }
+
+    Ok((color, count))
+}
+
+/// Read one set of colors into a HashMap with color as the key
+/// and count as the value
+/// Input should be a string in format 1 blue, 2 red, 3 green
+/// Every problem is added to problems, and reading goes on with the next color.
+fn read_set(
+    lines: &Lines,
+    i: usize,
+    input: &str,
+    palette: &Palette,
+    problems: &mut Vec<ParseError>,
+) -> HashMap<Color, u32> {
+    let mut res: HashMap<Color, u32> = HashMap::new();
+    if input.trim().is_empty() {
+        problems.push(lines.error(i, input, "empty set"));
+        return res;
+    }
+
+    let parts = input.split(',');
+    for part in parts {
+        let (name, count) = match parse_color_count(lines, i, part.trim()) {
+            Ok(color_count) => color_count,
+            Err(err) => {
+                problems.push(err);
+                continue;
+            }
+        };
+        let color = match palette.color(name) {
+            Some(color) => color,
+            None => {
+                let message = format!("unknown color '{}', expected one of {}", name, palette.list());
+                problems.push(lines.error(i, name, message));
+                continue;
+            }
+        };
+        if res.insert(color, count).is_some() {
+            problems.push(lines.error(i, name, format!("'{}' is listed twice in the set", name)));
+        }
+    }
+
+    res
+}
+
+/// Read the sets after the ':' of line i, separated by ';'.
+fn read_sets(lines: &Lines, i: usize, palette: &Palette, problems: &mut Vec<ParseError>) -> Vec<HashMap<Color, u32>> {
+    let input: &str = &lines[i];
+    let start_index = get_next_or_end(input, ':') + 1;
+
+    input[start_index..]
+        .split(';')
+        .map(|set| read_set(lines, i, set, palette, problems))
+        .collect()
+}
+
+/// Game on line i, or every problem of the line. The sets are still checked when the
+/// index cannot be read, as long as there is a ':'.
+fn read_game(lines: &Lines, i: usize, palette: &Palette) -> Result<Game, Vec<ParseError>> {
+    let mut problems: Vec<ParseError> = Vec::new();
+    let index = parse_index(lines, i).map_err(|err| problems.push(err)).ok();
+    let cube_sets = if lines[i].contains(':') {
+        read_sets(lines, i, palette, &mut problems)
+    } else {
+        Vec::new()
+    };
+
+    match index {
+        Some(index) if problems.is_empty() => Ok(Game { index, cube_sets }),
+        _ => Err(problems),
+    }
+}
+
+/// Game on line i, or its first problem.
+fn parse_game(lines: &Lines, i: usize, palette: &Palette) -> Result<Game, ParseError> {
+    read_game(lines, i, palette).map_err(|mut problems| problems.remove(0))
+}
+
+/// Parse a game log using only the colors of palette.
+pub fn parse_games(lines: &Lines, palette: &Palette) -> Result<Vec<Game>, ParseError> {
+    (0..lines.len()).map(|i| parse_game(lines, i, palette)).collect()
+}
+
+/// Parse a bag written like a set, e.g. "12 red, 13 green, 14 blue", from line i.
+/// Colors that are not in the palette yet are added to it.
+fn parse_bag(lines: &Lines, i: usize, palette: &mut Palette) -> Result<Bag, ParseError> {
+    let mut res: Bag = HashMap::new();
+    for part in lines[i].split(',') {
+        let (name, count) = parse_color_count(lines, i, part.trim())?;
+        if res.insert(palette.add(name), count).is_some() {
+            return Err(lines.error(i, name, format!("'{}' is listed twice", name)));
+        }
+    }
+    Ok(res)
+}
+
+/// Parse bags, one per line. Lines starting with '#' are comments.
+pub fn parse_bags(lines: &Lines, palette: &mut Palette) -> Result<Vec<Bag>, ParseError> {
+    let mut res: Vec<Bag> = Vec::new();
+    for i in 0..lines.len() {
+        if !lines[i].starts_with('#') {
+            res.push(parse_bag(lines, i, palette)?);
+        }
+    }
+    Ok(res)
+}
+
+/// The bag of the puzzle: 12 red, 13 green and 14 blue cubes.
+pub fn standard_bag() -> Bag {
+    HashMap::from([(Color::RED, 12), (Color::GREEN, 13), (Color::BLUE, 14)])
+}
+
+/// Cubes of each color drawn over all the sets of game.
+fn count_all(game: &Game) -> HashMap<Color, u32> {
+    let mut res: HashMap<Color, u32> = HashMap::new();
+    for set in game.cube_sets.iter() {
+        for (key, value) in set.iter() {
+            if res.contains_key(key) {
+                res.insert(*key, value + res.get(key).unwrap());
+            } else {
+                res.insert(*key, *value);
+            }
+        }
+    }
+    res
+}
+
+fn count_max(game: &Game) -> HashMap<Color, u32> {
+    let mut res: HashMap<Color, u32> = HashMap::new();
+
+    for set in game.cube_sets.iter() {
+        for (key, value) in set.iter() {
+            if res.get(key).is_none_or(|max| max < value) {
+                res.insert(*key, *value);
+            }
+        }
+    }
+    res
+}
+
+fn get_color_count(input: &HashMap<Color, u32>, color: Color) -> u32 {
+    let val: Option<&u32> = input.get(&color);
+    if val.is_none() {
+        return 0;
+    }
+    //println!("Color {} count: {}", color, val.unwrap());
+
+    *val.unwrap()
+}
+
+/// Whether a game with the given maximum counts could be played with bag.
+fn is_possible(max_cubes: &HashMap<Color, u32>, bag: &Bag) -> bool {
+    max_cubes
+        .iter()
+        .all(|(color, count)| *count <= get_color_count(bag, *color))
+}
+
+/// Sum of the indexes of games possible with the cubes in bag.
+pub fn possible_sum(games: &[Game], bag: &Bag) -> u32 {
+    let mut possible_sum: u32 = 0;
+
+    for game in games.iter() {
+        //print_game(game);
+        if is_possible(&count_max(game), bag) {
+            possible_sum += game.index;
+        }
+    }
+    possible_sum
+}
+
+/// Maximum counts of every game, worked out once for checking many bags.
+pub struct GameMaxima {
+    /// Index of the game and its maximum count of each color.
+    maxima: Vec<(u32, HashMap<Color, u32>)>,
+}
+
+impl GameMaxima {
+    pub fn new(games: &[Game]) -> GameMaxima {
+        let mut maxima: Vec<(u32, HashMap<Color, u32>)> = Vec::new();
+        for game in games.iter() {
+            maxima.push((game.index, count_max(game)));
+        }
+        GameMaxima { maxima }
+    }
+
+    /// Indexes of the games possible with the cubes in bag.
+    pub fn possible(&self, bag: &Bag) -> Vec<u32> {
+        let mut res: Vec<u32> = Vec::new();
+        for (index, max_cubes) in self.maxima.iter() {
+            if is_possible(max_cubes, bag) {
+                res.push(*index);
+            }
+        }
+        res
+    }
+}
+
+/// Bag written like a set, with its colors in the order of palette, e.g. "12 red, 14 blue".
+pub fn format_bag(bag: &Bag, palette: &Palette) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    for color in palette.colors() {
+        if let Some(count) = bag.get(&color) {
+            parts.push(format!("{} {}", count, palette.name(color)));
+        }
+    }
+    parts.join(", ")
+}
+
+/// Colors of palette drawn in at least one of games, in palette order.
+/// Colors only known from a bag do not count towards the power of a game.
+fn colors_drawn(games: &[Game], palette: &Palette) -> Vec<Color> {
+    palette
+        .colors()
+        .filter(|color| games.iter().any(|game| game.cube_sets.iter().any(|set| set.contains_key(color))))
+        .collect()
+}
+
+/// Sum of the powers of the minimum cube sets, multiplying the counts of every color of
+/// palette drawn in the game log.
+pub fn power_sum(games: &[Game], palette: &Palette) -> u64 {
+    let colors = colors_drawn(games, palette);
+    let mut power_sum: u64 = 0;
+
+    for game in games.iter() {
+        power_sum += power(&count_max(game), &colors);
+    }
+    power_sum
+}
+
+/// Product of the counts of colors.
+fn power(max_cubes: &HashMap<Color, u32>, colors: &[Color]) -> u64 {
+    let mut power: u64 = 1;
+    for color in colors.iter() {
+        power *= get_color_count(max_cubes, *color) as u64;
+    }
+    power
+}
+
+/// Summary of one game.
+pub struct GameReport {
+    pub index: u32,
+    /// Cubes of each color drawn over all the sets.
+    pub totals: HashMap<Color, u32>,
+    /// Fewest cubes of each color the game could be played with.
+    pub min_bag: HashMap<Color, u32>,
+    pub power: u64,
+    /// Whether the game could be played with the bag it was checked against.
+    pub possible: bool,
+}
+
+/// Summary of every game, checked against bag.
+pub fn game_reports(games: &[Game], palette: &Palette, bag: &Bag) -> Vec<GameReport> {
+    let colors = colors_drawn(games, palette);
+    let mut res: Vec<GameReport> = Vec::new();
+    for game in games.iter() {
+        let min_bag = count_max(game);
+        res.push(GameReport {
+            index: game.index,
+            totals: count_all(game),
+            power: power(&min_bag, &colors),
+            possible: is_possible(&min_bag, bag),
+            min_bag,
+        });
+    }
+    res
+}
+
+fn _print_set(set: &HashMap<Color, u32>) {
+    for (key, val) in set {
+        println!("{:?}: {}", key, val);
+    }
+}
+
+fn _print_game(game: &Game) {
+    println!("\n\nGame {}:", game.index);
+
+    for set in game.cube_sets.iter() {
+        _print_set(set);
+    }
+}
+
+pub struct Day02;
+
+impl Solution for Day02 {
+    const DAY: u8 = 2;
+    /// One game per line, blank lines dropped.
+    const NORMALIZE: Normalize = Normalize::TRIMMED;
+
+    type Input = Vec<Game>;
+
+    fn parse(input: &Lines) -> Result<Vec<Game>, ParseError> {
+        parse_games(input, &Palette::default())
+    }
+
+    /// Sum of the indexes of games possible with 12 red, 13 green and 14 blue cubes.
+    fn part1(games: &Vec<Game>) -> Answer {
+        possible_sum(games, &standard_bag()).into()
+    }
+
+    /// Sum of the powers of the minimum cube sets.
+    fn part2(games: &Vec<Game>) -> Answer {
+        power_sum(games, &Palette::default()).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse_example;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = parse_example::<Day02>(EXAMPLE);
+        assert_eq!(Day02::part1(&input), Answer::Number(8));
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = parse_example::<Day02>(EXAMPLE);
+        assert_eq!(Day02::part2(&input), Answer::Number(2286));
+    }
+
+    #[test]
+    fn count_max_takes_largest_of_each_color() {
+        let games = parse_example::<Day02>(EXAMPLE);
+        let max = count_max(&games[0]);
+        assert_eq!(max.get(&Color::RED), Some(&4));
+        assert_eq!(max.get(&Color::GREEN), Some(&2));
+        assert_eq!(max.get(&Color::BLUE), Some(&6));
+    }
+
+    #[test]
+    fn parse_error_points_at_count() {
+        let lines = Lines::new("bad", "Game 1: 3 blue, x red", Day02::NORMALIZE);
+        let err = Day02::parse(&lines).err().unwrap();
+        assert_eq!((err.line, err.column), (1, 17));
+    }
+
+    #[test]
+    fn unknown_color_is_an_error() {
+        let lines = Lines::new("bad", "Game 1: 3 blue, 2 rde", Day02::NORMALIZE);
+        let err = Day02::parse(&lines).err().unwrap();
+        assert_eq!((err.column, err.text.as_str()), (19, "rde"));
+        assert_eq!(err.message, "unknown color 'rde', expected one of red, green, blue");
+    }
+
+    #[test]
+    fn bag_with_extra_colors() {
+        let mut palette = Palette::default();
+        let lines = Lines::new("bag", "# variant\n1 red, 1 green, 1 blue, 2 yellow", Normalize::TRIMMED);
+        let bags = parse_bags(&lines, &mut palette).unwrap();
+        let yellow = palette.color("yellow").unwrap();
+        assert_eq!(bags.len(), 1);
+        assert_eq!(bags[0].get(&yellow), Some(&2));
+
+        let log = Lines::new("log", "Game 1: 1 red, 2 yellow\nGame 2: 3 yellow; 1 blue, 1 green", Day02::NORMALIZE);
+        let games = parse_games(&log, &palette).unwrap();
+        assert_eq!(possible_sum(&games, &bags[0]), 1);
+        assert_eq!(power_sum(&games, &palette), 0);
+    }
+
+    #[test]
+    fn bag_colors_do_not_change_the_power() {
+        let mut palette = Palette::default();
+        let lines = Lines::new("--bag", "12 red, 13 green, 14 blue, 3 yellow", Normalize::TRIMMED);
+        let bags = parse_bags(&lines, &mut palette).unwrap();
+        let games = parse_games(&Lines::new("example", EXAMPLE, Day02::NORMALIZE), &palette).unwrap();
+        assert_eq!(possible_sum(&games, &bags[0]), 8);
+        assert_eq!(power_sum(&games, &palette), 2286);
+        assert_eq!(game_reports(&games, &palette, &bags[0])[0].power, 48);
+    }
+
+    #[test]
+    fn bag_lists_every_color_once() {
+        let mut palette = Palette::default();
+        let lines = Lines::new("bag", "1 red, 2 red", Normalize::TRIMMED);
+        let err = parse_bags(&lines, &mut palette).err().unwrap();
+        assert_eq!((err.column, err.text.as_str()), (10, "red"));
+    }
+
+    #[test]
+    fn many_bags_from_one_reduction() {
+        let games = parse_example::<Day02>(EXAMPLE);
+        let maxima = GameMaxima::new(&games);
+        assert_eq!(maxima.possible(&standard_bag()), vec![1, 2, 5]);
+
+        let mut palette = Palette::default();
+        let lines = Lines::new("bags", "20 red, 13 green, 15 blue\n1 blue", Normalize::TRIMMED);
+        let bags = parse_bags(&lines, &mut palette).unwrap();
+        assert_eq!(maxima.possible(&bags[0]), vec![1, 2, 3, 4, 5]);
+        assert!(maxima.possible(&bags[1]).is_empty());
+    }
+
+    #[test]
+    fn format_bag_in_palette_order() {
+        let bag = HashMap::from([(Color::BLUE, 14), (Color::RED, 12)]);
+        assert_eq!(format_bag(&bag, &Palette::default()), "12 red, 14 blue");
+    }
+
+    #[test]
+    fn report_of_example() {
+        let games = parse_example::<Day02>(EXAMPLE);
+        let reports = game_reports(&games, &Palette::default(), &standard_bag());
+        assert_eq!(reports.len(), 5);
+
+        let first = &reports[0];
+        assert_eq!(first.totals.get(&Color::RED), Some(&5));
+        assert_eq!(first.totals.get(&Color::GREEN), Some(&4));
+        assert_eq!(first.totals.get(&Color::BLUE), Some(&9));
+        assert_eq!(first.min_bag, count_max(&games[0]));
+        assert_eq!((first.power, first.possible), (48, true));
+        assert_eq!((reports[2].power, reports[2].possible), (1560, false));
+    }
+}
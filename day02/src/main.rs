@@ -1,208 +1,213 @@
+use aoc_common::{csv_field, or_exit, parse_inputfile, print_table, Args, Lines, Normalize, Solution};
+use serde::ser::{SerializeMap, Serializer};
+use serde::Serialize;
 use std::collections::HashMap;
-use std::env;
-use std::fs::read_to_string;
-use std::option::Option;
-
-struct Game {
-    cube_sets: Vec<HashMap<String, u32>>,
-    index: u32,
-}
-
-/// Read file filename into a vector, with each line as on element.
-fn parse_inputfile(filename: &String) -> Vec<String> {
-    if filename.is_empty() {
-        return Vec::new();
-    }
-
-    let mut result: Vec<String> = Vec::new();
-    for line in read_to_string(filename).unwrap().lines() {
-        result.push(line.to_string());
-    }
-    result
-}
-
-fn get_filename() -> String {
-    for (i, arg) in env::args().enumerate() {
-        if i == 1 {
-            return arg.to_string();
+use std::process::exit;
+use std::str::FromStr;
+use day02::{
+    estimate_bag, format_bag, game_reports, parse_bags, parse_games, possible_sum, power_sum, standard_bag,
+    validate_games, Bag, Color, Day02, Estimate, GameMaxima, GameReport, Palette,
+};
+
+/// Largest count of a color searched by --estimate, unless given with --max-count.
+const MAX_COUNT: u32 = 100;
+
+/// Bag given with --bag or --bag-file, or else the one of the puzzle.
+/// Colors of the bag are added to the palette.
+fn read_bag(args: &mut Args, palette: &mut Palette) -> Result<Bag, String> {
+    let lines = if let Some(text) = args.value("--bag")? {
+        Lines::new("--bag", &text, Normalize::TRIMMED)
+    } else if let Some(filename) = args.value("--bag-file")? {
+        parse_inputfile(&filename, Normalize::TRIMMED)?
+    } else {
+        return Ok(standard_bag());
+    };
+
+    let mut bags = parse_bags(&lines, palette).map_err(|err| err.to_string())?;
+    if bags.len() != 1 {
+        return Err(format!("{} should give exactly one bag, not {}", lines.name(), bags.len()));
+    }
+    Ok(bags.remove(0))
+}
+
+/// Candidate bags of --bags, one per line, if given.
+fn read_candidates(args: &mut Args, palette: &mut Palette) -> Result<Option<Vec<Bag>>, String> {
+    let filename = match args.value("--bags")? {
+        Some(filename) => filename,
+        None => return Ok(None),
+    };
+    let lines = parse_inputfile(&filename, Normalize::TRIMMED)?;
+    let bags = parse_bags(&lines, palette).map_err(|err| err.to_string())?;
+    Ok(Some(bags))
+}
+
+/// Estimate of a game on one line, e.g.
+/// "Game 1: 4 red (4-9), 2 green (2-6), 6 blue (6-100+), log-likelihood -3.42".
+/// Intervals reaching the largest count searched are marked with '+'.
+fn format_estimate(estimate: &Estimate, palette: &Palette) -> String {
+    let mut parts: Vec<String> = Vec::new();
+    for color in estimate.colors.iter() {
+        let open = if color.high == estimate.max_count { "+" } else { "" };
+        let name = palette.name(color.color);
+        parts.push(format!("{} {} ({}-{}{})", color.count, name, color.low, color.high, open));
+    }
+    format!(
+        "Game {}: {}, log-likelihood {:.2}",
+        estimate.index,
+        parts.join(", "),
+        estimate.log_likelihood
+    )
+}
+
+/// How --report prints the summary of every game.
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ReportFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+impl FromStr for ReportFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<ReportFormat, String> {
+        match s {
+            "table" => Ok(ReportFormat::Table),
+            "json" => Ok(ReportFormat::Json),
+            "csv" => Ok(ReportFormat::Csv),
+            _ => Err(format!("Unknown report format {}, expected table, json or csv", s)),
         }
     }
-    String::new()
-}
-
-/// Get index of the game
-fn parse_index(input: &String) -> Option<u32> {
-    if !input.starts_with("Game ") {
-        return None;
-    }
-
-    let end: Option<usize> = input.find(':');
-    if end.is_some() {
-        let indexstr = &input[5..end.unwrap()].to_string();
-
-        return Some(indexstr.to_string().parse().unwrap());
-    }
-
-    None
 }
 
-fn get_next_or_end(input: &String, limiter: char) -> usize {
-    let limiter_index: Option<usize> = input.find(limiter);
-
-    if limiter_index.is_none() {
-        return input.len();
-    }
-
-    limiter_index.unwrap()
+/// Counts by color name in the order of the palette, with 0 for colors not drawn.
+struct ColorCounts<'a> {
+    counts: &'a HashMap<Color, u32>,
+    palette: &'a Palette,
 }
 
-/// Input should be in format 'count color,'
-fn parse_color_count(input: &String) -> (String, u32) {
-    let mut color: String = String::new();
-    let mut count: u32 = 0;
-
-    let parts: std::str::Split<'_, char> = input.split(' ');
-    for (i, part) in parts.enumerate() {
-        if i == 1 {
-            color = part.to_string();
-        } else {
-            count = part.to_string().parse().unwrap();
+impl Serialize for ColorCounts<'_> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let mut map = serializer.serialize_map(None)?;
+        for color in self.palette.colors() {
+            map.serialize_entry(self.palette.name(color), self.counts.get(&color).unwrap_or(&0))?;
+        }
+        map.end()
+    }
+}
+
+/// Summary of one game in JSON.
+#[derive(Serialize)]
+struct ReportRecord<'a> {
+    game: u32,
+    totals: ColorCounts<'a>,
+    min_bag: ColorCounts<'a>,
+    power: u64,
+    possible: bool,
+}
+
+fn print_report(reports: &[GameReport], palette: &Palette, format: ReportFormat) {
+    if format == ReportFormat::Json {
+        let mut records: Vec<ReportRecord> = Vec::new();
+        for report in reports.iter() {
+            records.push(ReportRecord {
+                game: report.index,
+                totals: ColorCounts { counts: &report.totals, palette },
+                min_bag: ColorCounts { counts: &report.min_bag, palette },
+                power: report.power,
+                possible: report.possible,
+            });
+        }
+        println!("{}", serde_json::to_string_pretty(&records).unwrap());
+        return;
+    }
+
+    let names: Vec<&str> = palette.colors().map(|color| palette.name(color)).collect();
+    let mut rows: Vec<Vec<String>> = Vec::new();
+    let mut header: Vec<String> = vec!["game".to_string()];
+    header.extend(names.iter().map(|name| format!("total_{}", name)));
+    header.extend(names.iter().map(|name| format!("min_{}", name)));
+    header.extend(["power".to_string(), "possible".to_string()]);
+    rows.push(header);
+
+    for report in reports.iter() {
+        let mut row: Vec<String> = vec![report.index.to_string()];
+        for counts in [&report.totals, &report.min_bag] {
+            for color in palette.colors() {
+                row.push(counts.get(&color).unwrap_or(&0).to_string());
+            }
         }
+        row.extend([report.power.to_string(), report.possible.to_string()]);
+        rows.push(row);
     }
 
-    (color, count)
-}
-
-/// Parse one set of colors into a HashMap with color as the key
-/// and count as the value
-/// Input should be a string in format 1 blue, 2 red, 3 green
-fn parse_set(input: &String) -> HashMap<String, u32> {
-    let mut res: HashMap<String, u32> = HashMap::new();
-    let parts = input.split(',');
-    for part in parts {
-        let output = parse_color_count(&part.to_string().trim().to_string());
-        res.insert(output.0, output.1);
+    if format == ReportFormat::Table {
+        print_table(&rows);
+        return;
     }
-
-    res
-}
-
-fn parse_sets(input: &String) -> Option<Vec<HashMap<String, u32>>> {
-    let mut res: Vec<HashMap<String, u32>> = Vec::new();
-    let mut start_index = get_next_or_end(input, ':') + 1;
-    let mut end_index = start_index - 1;
-
-    while start_index < input.len() {
-        end_index += get_next_or_end(&input[start_index..].to_string(), ';') + 1;
-        res.push(parse_set(&input[start_index + 1..end_index].to_string()));
-        start_index = end_index + 1;
+    for row in rows.iter() {
+        let fields: Vec<String> = row.iter().map(|field| csv_field(field)).collect();
+        println!("{}", fields.join(","));
     }
-
-    Some(res)
 }
 
-fn parse_game(input: &String) -> Option<Game> {
-    let index = parse_index(input);
-    let cube_sets = parse_sets(input);
-
-    Some(Game {
-        cube_sets: cube_sets.unwrap(),
-        index: index.unwrap(),
-    })
-}
-
-fn parse_games(input: Vec<String>) -> Vec<Game> {
-    let mut res: Vec<Game> = Vec::new();
-    for line in input.iter() {
-        let parsed: Option<Game> = parse_game(line);
-        if parsed.is_some() {
-            res.push(parsed.unwrap());
-        }
-    }
-    res
-}
-
-fn count_all(game: &Game) -> HashMap<String, u32> {
-    let mut res: HashMap<String, u32> = HashMap::new();
-    for set in game.cube_sets.iter() {
-        for (key, value) in set.iter() {
-            if res.contains_key(key) {
-                res.insert(key.to_string(), value + res.get(key).unwrap());
-            } else {
-                res.insert(key.to_string(), value.clone());
+fn main() {
+    let mut args = Args::from_env();
+    let mut palette = Palette::default();
+    let bag = or_exit(read_bag(&mut args, &mut palette));
+    let candidates = or_exit(read_candidates(&mut args, &mut palette));
+    let estimate = args.flag("--estimate");
+    let report: Option<ReportFormat> = or_exit(args.value("--report")).map(|format| or_exit(format.parse()));
+    // Strict validation needs the lines exactly as they are, to see stray whitespace.
+    let strict = args.flag("--strict");
+    let normalize = if strict { Normalize::RAW } else { Day02::NORMALIZE };
+    let max_count: u32 = match or_exit(args.value("--max-count")) {
+        Some(value) => or_exit(value.parse().map_err(|_| "Invalid value for --max-count")),
+        None => MAX_COUNT,
+    };
+
+    args.for_each_input(normalize, |input| {
+        if strict {
+            let problems = validate_games(input, &palette);
+            for err in problems.iter() {
+                eprintln!("{}\n", err);
+            }
+            if !problems.is_empty() {
+                let plural = if problems.len() == 1 { "" } else { "s" };
+                eprintln!("{} problem{} in {}", problems.len(), plural, input.name());
+                exit(1);
             }
         }
-    }
-    res
-}
 
-fn count_max(game: &Game) -> HashMap<String, u32> {
-    let mut res: HashMap<String, u32> = HashMap::new();
+        let games = or_exit(parse_games(input, &palette));
 
-    for set in game.cube_sets.iter() {
-        for (key, value) in set.iter() {
-            if !res.contains_key(key) {
-                res.insert(key.to_string(), value.clone());
-            } else if res.get(key).unwrap() < value {
-                res.insert(key.to_string(), value.clone());
+        // The games are reduced to their maximum counts once, and checked against every bag.
+        if let Some(candidates) = &candidates {
+            let maxima = GameMaxima::new(&games);
+            for bag in candidates.iter() {
+                let possible: Vec<u32> = maxima.possible(bag);
+                let indexes: Vec<String> = possible.iter().map(|index| index.to_string()).collect();
+                let sum: u32 = possible.iter().sum();
+                println!("{}: games [{}], sum {}", format_bag(bag, &palette), indexes.join(", "), sum);
             }
+            return;
         }
-    }
-    res
-}
 
-fn get_color_count(input: &HashMap<String, u32>, color: String) -> u32 {
-    let val: Option<&u32> = input.get(&color);
-    if val.is_none() {
-        return 0;
-    }
-    //println!("Color {} count: {}", color, val.unwrap());
-
-    val.unwrap().clone()
-}
-
-fn _print_set(set: &HashMap<String, u32>) {
-    for (key, val) in set {
-        println!("{}: {}", key, val);
-    }
-}
-
-fn _print_game(game: &Game) {
-    println!("\n\nGame {}:", game.index);
-
-    for set in game.cube_sets.iter() {
-        print_set(set);
-    }
-}
+        // Totals, minimum bag, power and whether it is possible under the bag, for every game.
+        if let Some(format) = report {
+            print_report(&game_reports(&games, &palette, &bag), &palette, format);
+            return;
+        }
 
-fn main() {
-    let lim_red = 12;
-    let lim_green = 13;
-    let lim_blue = 14;
-
-    let input: Vec<String> = parse_inputfile(&get_filename());
-    let games: Vec<Game> = parse_games(input);
-    let mut impossible_sum: u32 = 0;
-    let mut possible_sum: u32 = 0;
-    let mut power_sum: u64 = 0;
-
-    for game in games.iter() {
-        //print_game(game);
-        let max_cubes = count_max(game);
-        let red = get_color_count(&max_cubes, "red".to_string());
-        let blue = get_color_count(&max_cubes, "blue".to_string());
-        let green = get_color_count(&max_cubes, "green".to_string());
-
-        if (red > lim_red) | (blue > lim_blue) | (green > lim_green) {
-            impossible_sum += game.index;
-        } else {
-            possible_sum += game.index;
+        // Most likely bag of every game, with 95% confidence intervals of the counts.
+        if estimate {
+            for game in games.iter() {
+                println!("{}", format_estimate(&estimate_bag(game, &palette, max_count), &palette));
+            }
+            return;
         }
-        let power = red * blue * green;
-        power_sum += power as u64;
-    }
 
-    println!("Impossible sum: {}", impossible_sum);
-    println!("Possible sum: {}", possible_sum);
-    println!("Power sum: {}", power_sum);
+        println!("Possible sum: {}", possible_sum(&games, &bag));
+        println!("Power sum: {}", power_sum(&games, &palette));
+    });
 }
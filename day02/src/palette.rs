@@ -0,0 +1,87 @@
+/// Color of a cube, an index into a `Palette`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Color(usize);
+
+impl Color {
+    /// Colors of the standard palette.
+    pub const RED: Color = Color(0);
+    pub const GREEN: Color = Color(1);
+    pub const BLUE: Color = Color(2);
+}
+
+/// Names of the cube colors a game log may use.
+/// Variants of the puzzle add their colors to the standard red, green and blue.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Palette {
+    names: Vec<String>,
+}
+
+impl Default for Palette {
+    fn default() -> Palette {
+        Palette::new(&["red", "green", "blue"])
+    }
+}
+
+impl Palette {
+    pub fn new(names: &[&str]) -> Palette {
+        let mut res = Palette { names: Vec::new() };
+        for name in names {
+            res.add(name);
+        }
+        res
+    }
+
+    /// Color called name, if it is in the palette.
+    pub fn color(&self, name: &str) -> Option<Color> {
+        self.names.iter().position(|n| n == name).map(Color)
+    }
+
+    /// Color called name, added to the palette if it is new.
+    pub fn add(&mut self, name: &str) -> Color {
+        match self.color(name) {
+            Some(color) => color,
+            None => {
+                self.names.push(name.to_string());
+                Color(self.names.len() - 1)
+            }
+        }
+    }
+
+    pub fn name(&self, color: Color) -> &str {
+        &self.names[color.0]
+    }
+
+    /// Every color, in the order they were added.
+    pub fn colors(&self) -> impl Iterator<Item = Color> {
+        (0..self.names.len()).map(Color)
+    }
+
+    /// Names of the colors, separated by commas.
+    pub fn list(&self) -> String {
+        self.names.join(", ")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn standard_colors() {
+        let palette = Palette::default();
+        assert_eq!(palette.color("red"), Some(Color::RED));
+        assert_eq!(palette.color("green"), Some(Color::GREEN));
+        assert_eq!(palette.color("blue"), Some(Color::BLUE));
+        assert_eq!(palette.color("Blue"), None);
+    }
+
+    #[test]
+    fn add_keeps_existing_colors() {
+        let mut palette = Palette::default();
+        let yellow = palette.add("yellow");
+        assert_eq!(palette.add("red"), Color::RED);
+        assert_eq!(palette.add("yellow"), yellow);
+        assert_eq!(palette.name(yellow), "yellow");
+        assert_eq!(palette.colors().count(), 4);
+    }
+}
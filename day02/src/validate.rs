@@ -0,0 +1,148 @@
+use crate::{read_game, Palette};
+use aoc_common::{Lines, ParseError};
+use std::collections::HashMap;
+
+/// Stray whitespace on line i: at either end, more than one space, a space before a
+/// separator, or whitespace other than a space.
+fn check_whitespace(lines: &Lines, i: usize) -> Vec<ParseError> {
+    let line: &str = &lines[i];
+    let mut res: Vec<ParseError> = Vec::new();
+    let mut chars = line.char_indices().peekable();
+
+    while let Some((start, c)) = chars.next() {
+        if !c.is_whitespace() {
+            continue;
+        }
+        let mut end = start + c.len_utf8();
+        while let Some((i, c)) = chars.next_if(|(_, c)| c.is_whitespace()) {
+            end = i + c.len_utf8();
+        }
+        let run: &str = &line[start..end];
+
+        let message = if start == 0 {
+            "whitespace at the start of the line".to_string()
+        } else if end == line.len() {
+            "whitespace at the end of the line".to_string()
+        } else if run != " " {
+            "expected a single space".to_string()
+        } else {
+            match line[end..].chars().next() {
+                Some(next @ (',' | ';' | ':')) => format!("space before '{}'", next),
+                _ => continue,
+            }
+        };
+        res.push(lines.error(i, run, message));
+    }
+    res
+}
+
+/// Every problem of a game log read with `Normalize::RAW`, in the order of the lines.
+/// Stricter than parsing: blank lines, stray whitespace and games that are not numbered
+/// 1, 2, 3... are reported too. A line with whitespace problems is not parsed any further.
+pub fn validate_games(lines: &Lines, palette: &Palette) -> Vec<ParseError> {
+    let mut res: Vec<ParseError> = Vec::new();
+    // Line number of every game seen, by index.
+    let mut seen: HashMap<u32, usize> = HashMap::new();
+    // Index of the last game, or None after a line that could not be read.
+    let mut previous: Option<u32> = Some(0);
+
+    for i in 0..lines.len() {
+        let line: &str = &lines[i];
+        if line.trim().is_empty() {
+            res.push(lines.error(i, line, "blank line"));
+            continue;
+        }
+
+        let problems = check_whitespace(lines, i);
+        if !problems.is_empty() {
+            res.extend(problems);
+            previous = None;
+            continue;
+        }
+
+        let game = match read_game(lines, i, palette) {
+            Ok(game) => game,
+            Err(problems) => {
+                res.extend(problems);
+                previous = None;
+                continue;
+            }
+        };
+
+        let index_text: &str = &line["Game ".len()..line.find(':').unwrap()];
+        if let Some(first) = seen.get(&game.index) {
+            let message = format!("game {} is listed twice, first on line {}", game.index, first);
+            res.push(lines.error(i, index_text, message));
+        } else {
+            if let Some(expected) = previous.map(|index| index + 1) {
+                if game.index != expected {
+                    let message = format!("expected game {}, the games should be numbered from 1 up", expected);
+                    res.push(lines.error(i, index_text, message));
+                }
+            }
+            seen.insert(game.index, lines.number(i));
+        }
+        previous = Some(game.index);
+    }
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::Normalize;
+
+    /// Line, column and message of every problem in text.
+    fn problems(text: &str) -> Vec<(usize, usize, String)> {
+        let lines = Lines::new("log", text, Normalize::RAW);
+        validate_games(&lines, &Palette::default())
+            .into_iter()
+            .map(|err| (err.line, err.column, err.message))
+            .collect()
+    }
+
+    #[test]
+    fn example_is_valid() {
+        assert!(problems(include_str!("../fixtures/example.txt")).is_empty());
+    }
+
+    #[test]
+    fn duplicate_colors_and_empty_sets() {
+        let text = "Game 1: 3 blue, 4 blue\nGame 2: 1 red;; 2 green\nGame 3: 1 red;";
+        let expected = vec![
+            (1, 19, "'blue' is listed twice in the set".to_string()),
+            (2, 15, "empty set".to_string()),
+            (3, 15, "empty set".to_string()),
+        ];
+        assert_eq!(problems(text), expected);
+    }
+
+    #[test]
+    fn every_problem_of_a_line() {
+        let found = problems("Game 1: 3 blue, 4 blue;; 1 rde\nGame 2 3 red");
+        let expected = vec![
+            (1, 19, "'blue' is listed twice in the set".to_string()),
+            (1, 24, "empty set".to_string()),
+            (1, 28, "unknown color 'rde', expected one of red, green, blue".to_string()),
+            (2, 13, "expected ':' after the game index".to_string()),
+        ];
+        assert_eq!(found, expected);
+    }
+
+    #[test]
+    fn game_indexes() {
+        let text = "Game 1: 1 red\nGame 3: 1 red\nGame 1: 1 red\nGame x: 1 red";
+        let found = problems(text);
+        assert_eq!(found.len(), 3);
+        assert_eq!(found[0], (2, 6, "expected game 2, the games should be numbered from 1 up".to_string()));
+        assert_eq!(found[1], (3, 6, "game 1 is listed twice, first on line 1".to_string()));
+        assert_eq!((found[2].0, found[2].1), (4, 6));
+    }
+
+    #[test]
+    fn stray_whitespace() {
+        let text = " Game 1: 1 red \nGame 2:  1 red , 2\tblue\n\nGame 3: 1 red";
+        let found: Vec<(usize, usize)> = problems(text).iter().map(|p| (p.0, p.1)).collect();
+        assert_eq!(found, vec![(1, 1), (1, 15), (2, 8), (2, 15), (2, 19), (3, 1)]);
+    }
+}
@@ -0,0 +1,165 @@
+//! Day 3: Gear Ratios
+
+mod render;
+mod rules;
+mod schematic;
+mod stream;
+
+pub use render::{render_ansi, render_html, styled_grid, Style};
+pub use rules::{Aggregate, Neighbours, Rule, Rules};
+pub use schematic::{Counting, Neighbourhood, Number, Schematic, Symbol};
+pub use stream::{stream_schematic_sums, SchematicSums};
+
+use aoc_common::{Answer, Lines, Normalize, ParseError, Solution};
+
+fn is_num(c: char) -> bool {
+    c.is_ascii_digit()
+}
+
+/// Parse number and length of number from String beginning with a number.
+/// input is a slice of line i.
+fn parse_num(lines: &Lines, i: usize, input: &str) -> Result<(u32, usize), ParseError> {
+    let mut end: usize = 0;
+    for c in input.chars() {
+        if is_num(c) {
+            end += 1;
+        } else {
+            break;
+        }
+    }
+
+    let val: u32 = lines.parse(i, &input[..end])?;
+    if end > 1 && input.starts_with('0') {
+        return Err(lines.error(i, &input[..end], "numbers with leading zeros are not supported"));
+    }
+    Ok((val, end))
+}
+
+/// Numbers and symbols of line row, with columns counted in characters.
+pub(crate) fn parse_row(
+    lines: &Lines,
+    row: usize,
+    numbers: &mut Vec<Number>,
+    symbols: &mut Vec<Symbol>,
+) -> Result<usize, ParseError> {
+    let input: &str = &lines[row];
+    let mut column: usize = 0;
+    let mut chars = input.char_indices().peekable();
+
+    while let Some((i, c)) = chars.next() {
+        if is_num(c) {
+            let (value, len) = parse_num(lines, row, &input[i..])?;
+            numbers.push(Number {
+                value,
+                row,
+                start: column,
+                end: column + len,
+            });
+            // The rest of the digits are ASCII, one byte each.
+            while chars.next_if(|(_, c)| is_num(*c)).is_some() {}
+            column += len;
+            continue;
+        }
+
+        if c != '.' {
+            symbols.push(Symbol { c, row, column });
+        }
+        column += 1;
+    }
+    Ok(column)
+}
+
+fn parse_to_struct(input: &Lines) -> Result<Schematic, ParseError> {
+    let mut numbers: Vec<Number> = Vec::new();
+    let mut symbols: Vec<Symbol> = Vec::new();
+    let mut width: Option<usize> = None;
+
+    for i in 0..input.len() {
+        let row_width = parse_row(input, i, &mut numbers, &mut symbols)?;
+
+        match width {
+            Some(width) if width != row_width => {
+                return Err(input.error(i, &input[i], format!("row is {} wide, expected {}", row_width, width)));
+            }
+            _ => width = Some(row_width),
+        }
+    }
+    Ok(Schematic::new(width.unwrap_or(0), input.len(), numbers, symbols))
+}
+
+pub struct Day03;
+
+impl Solution for Day03 {
+    const DAY: u8 = 3;
+    /// One row of the schematic per line, blank lines dropped.
+    const NORMALIZE: Normalize = Normalize::TRIMMED;
+
+    type Input = Schematic;
+
+    fn parse(input: &Lines) -> Result<Schematic, ParseError> {
+        parse_to_struct(input)
+    }
+
+    /// Sum of all part numbers next to a symbol.
+    fn part1(schematic: &Schematic) -> Answer {
+        schematic.part_sum(Counting::Once).into()
+    }
+
+    /// Sum of the gear ratios.
+    fn part2(schematic: &Schematic) -> Answer {
+        Rules::gears().total(schematic).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse_example;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = parse_example::<Day03>(EXAMPLE);
+        assert_eq!(Day03::part1(&input), Answer::Number(4361));
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = parse_example::<Day03>(EXAMPLE);
+        assert_eq!(Day03::part2(&input), Answer::Number(467835));
+    }
+
+    #[test]
+    fn rows_of_different_width_are_an_error() {
+        let lines = Lines::new("bad", "467..\n...*\n", Day03::NORMALIZE);
+        let err = Day03::parse(&lines).err().unwrap();
+        assert_eq!(err.line, 2);
+    }
+
+    #[test]
+    fn numbers_and_symbols_with_positions() {
+        let input = parse_example::<Day03>(EXAMPLE);
+        assert_eq!((input.width, input.height), (10, 10));
+        assert_eq!(input.numbers.len(), 10);
+        assert_eq!(input.numbers[1], Number { value: 114, row: 0, start: 5, end: 8 });
+        assert_eq!(input.symbols[0], Symbol { c: '*', row: 1, column: 3 });
+        assert_eq!(input.numbers_next_to(0), &[0, 2]);
+    }
+
+    #[test]
+    fn parts_do_not_depend_on_each_other() {
+        let input = parse_example::<Day03>(EXAMPLE);
+        assert_eq!(Day03::part2(&input), Answer::Number(467835));
+        assert_eq!(Day03::part1(&input), Answer::Number(4361));
+        assert_eq!(Day03::part2(&input), Answer::Number(467835));
+    }
+
+    #[test]
+    fn number_at_the_end_of_a_row() {
+        let lines = Lines::new("edge", "..*\n.12", Day03::NORMALIZE);
+        let input = Day03::parse(&lines).unwrap();
+        assert_eq!(input.numbers[0], Number { value: 12, row: 1, start: 1, end: 3 });
+        assert_eq!(Day03::part1(&input), Answer::Number(12));
+    }
+}
@@ -0,0 +1,186 @@
+//! Rendering of a schematic showing what the solver made of it: part numbers, numbers
+//! next to no symbol, gears and the two numbers of each gear.
+
+use crate::{Rules, Schematic};
+
+/// What a cell of the schematic is to the solver.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Style {
+    /// '.'
+    Empty,
+    /// Digit of a number next to a symbol.
+    Part,
+    /// Digit of a number next to no symbol.
+    NotPart,
+    /// Digit of a number next to a gear.
+    GearPart,
+    Symbol,
+    Gear,
+}
+
+impl Style {
+    /// SGR parameters of the style, or None for the default.
+    fn ansi(&self) -> Option<&'static str> {
+        match self {
+            Style::Empty => None,
+            Style::Part => Some("32"),
+            Style::NotPart => Some("31"),
+            Style::GearPart => Some("1;33"),
+            Style::Symbol => Some("1"),
+            Style::Gear => Some("1;30;43"),
+        }
+    }
+
+    /// CSS class of the style, or None for the default.
+    fn class(&self) -> Option<&'static str> {
+        match self {
+            Style::Empty => None,
+            Style::Part => Some("part"),
+            Style::NotPart => Some("not-part"),
+            Style::GearPart => Some("gear-part"),
+            Style::Symbol => Some("symbol"),
+            Style::Gear => Some("gear"),
+        }
+    }
+}
+
+/// Every cell of schematic with its style, row by row.
+pub fn styled_grid(schematic: &Schematic) -> Vec<Vec<(char, Style)>> {
+    let mut grid: Vec<Vec<(char, Style)>> = vec![vec![('.', Style::Empty); schematic.width]; schematic.height];
+    let gears = Rules::gears();
+
+    let mut gear_parts: Vec<bool> = vec![false; schematic.numbers.len()];
+    for (s, symbol) in schematic.symbols.iter().enumerate() {
+        let style = if gears.value(schematic, s).is_some() {
+            for i in schematic.numbers_next_to(s) {
+                gear_parts[*i] = true;
+            }
+            Style::Gear
+        } else {
+            Style::Symbol
+        };
+        grid[symbol.row][symbol.column] = (symbol.c, style);
+    }
+
+    for (i, number) in schematic.numbers.iter().enumerate() {
+        let style = if gear_parts[i] {
+            Style::GearPart
+        } else if schematic.symbols_next_to(i).is_empty() {
+            Style::NotPart
+        } else {
+            Style::Part
+        };
+        for (column, c) in (number.start..number.end).zip(number.value.to_string().chars()) {
+            grid[number.row][column] = (c, style);
+        }
+    }
+    grid
+}
+
+/// Runs of cells of the same style in a row, as text and style.
+fn runs(row: &[(char, Style)]) -> Vec<(String, Style)> {
+    let mut res: Vec<(String, Style)> = Vec::new();
+    for (c, style) in row.iter() {
+        match res.last_mut() {
+            Some((text, last)) if last == style => text.push(*c),
+            _ => res.push((c.to_string(), *style)),
+        }
+    }
+    res
+}
+
+/// Schematic with ANSI colors for a terminal: part numbers in green, other numbers in
+/// red, gears and their numbers in yellow and other symbols in bold.
+pub fn render_ansi(schematic: &Schematic) -> String {
+    let mut res = String::new();
+    for row in styled_grid(schematic).iter() {
+        for (text, style) in runs(row) {
+            match style.ansi() {
+                Some(sgr) => res.push_str(&format!("\x1b[{}m{}\x1b[0m", sgr, text)),
+                None => res.push_str(&text),
+            }
+        }
+        res.push('\n');
+    }
+    res
+}
+
+fn escape_html(text: &str) -> String {
+    let mut res = String::new();
+    for c in text.chars() {
+        match c {
+            '&' => res.push_str("&amp;"),
+            '<' => res.push_str("&lt;"),
+            '>' => res.push_str("&gt;"),
+            '"' => res.push_str("&quot;"),
+            _ => res.push(c),
+        }
+    }
+    res
+}
+
+const HTML_STYLE: &str = "\
+body { background: #1e1e1e; color: #777; }
+pre { font-size: 14px; line-height: 1.2; }
+.part { color: #4ec94e; }
+.not-part { color: #e05252; }
+.gear-part { color: #f0c030; font-weight: bold; }
+.symbol { color: #fff; font-weight: bold; }
+.gear { color: #1e1e1e; background: #f0c030; font-weight: bold; }
+";
+
+/// Standalone HTML page of the schematic, in the colors of `render_ansi`.
+pub fn render_html(schematic: &Schematic, title: &str) -> String {
+    let title = escape_html(title);
+    let mut res = String::new();
+    res.push_str("<!DOCTYPE html>\n<html>\n<head>\n<meta charset=\"utf-8\">\n");
+    res.push_str(&format!("<title>{}</title>\n<style>\n{}</style>\n", title, HTML_STYLE));
+    res.push_str("</head>\n<body>\n");
+    res.push_str(&format!("<h1>{}</h1>\n<pre>", title));
+    for row in styled_grid(schematic).iter() {
+        for (text, style) in runs(row) {
+            match style.class() {
+                Some(class) => res.push_str(&format!("<span class=\"{}\">{}</span>", class, escape_html(&text))),
+                None => res.push_str(&escape_html(&text)),
+            }
+        }
+        res.push('\n');
+    }
+    res.push_str("</pre>\n</body>\n</html>\n");
+    res
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day03;
+    use aoc_common::{Lines, Solution};
+
+    fn schematic(text: &str) -> Schematic {
+        Day03::parse(&Lines::new("test", text, Day03::NORMALIZE)).unwrap()
+    }
+
+    #[test]
+    fn styles_of_the_cells() {
+        let grid = styled_grid(&schematic("12*3.\n....7\n<.9.."));
+        let styles: Vec<Style> = grid.iter().map(|row| row[0].1).collect();
+        assert_eq!(styles, vec![Style::GearPart, Style::Empty, Style::Symbol]);
+        assert_eq!(grid[0][2], ('*', Style::Gear));
+        assert_eq!(grid[1][4], ('7', Style::NotPart));
+        assert_eq!(grid[2][2], ('9', Style::NotPart));
+    }
+
+    #[test]
+    fn ansi_colors_runs_and_keeps_the_text() {
+        let rendered = render_ansi(&schematic("12*3..\n....5#"));
+        assert!(rendered.starts_with("\x1b[1;33m12\x1b[0m\x1b[1;30;43m*\x1b[0m"));
+        assert!(rendered.ends_with("....\x1b[32m5\x1b[0m\x1b[1m#\x1b[0m\n"));
+    }
+
+    #[test]
+    fn html_escapes_symbols() {
+        let rendered = render_html(&schematic("<1.\n..."), "a & b");
+        assert!(rendered.contains("<title>a &amp; b</title>"));
+        assert!(rendered.contains("<span class=\"symbol\">&lt;</span><span class=\"part\">1</span>."));
+    }
+}
@@ -0,0 +1,277 @@
+use crate::Schematic;
+use aoc_common::{Lines, ParseError};
+use std::fmt;
+use std::str::FromStr;
+
+/// How the numbers next to a symbol are combined into its value.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Aggregate {
+    Sum,
+    Product,
+    Max,
+    Min,
+}
+
+impl FromStr for Aggregate {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Aggregate, String> {
+        match s {
+            "sum" => Ok(Aggregate::Sum),
+            "product" => Ok(Aggregate::Product),
+            "max" => Ok(Aggregate::Max),
+            "min" => Ok(Aggregate::Min),
+            _ => Err("expected sum, product, max or min".to_string()),
+        }
+    }
+}
+
+impl fmt::Display for Aggregate {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let name = match self {
+            Aggregate::Sum => "sum",
+            Aggregate::Product => "product",
+            Aggregate::Max => "max",
+            Aggregate::Min => "min",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl Aggregate {
+    fn apply(&self, values: impl Iterator<Item = u64>) -> u64 {
+        match self {
+            Aggregate::Sum => values.sum(),
+            Aggregate::Product => values.fold(1, |product, value| product.saturating_mul(value)),
+            Aggregate::Max => values.max().unwrap_or(0),
+            Aggregate::Min => values.min().unwrap_or(0),
+        }
+    }
+}
+
+/// How many numbers must be next to a symbol for its rule to apply.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighbours {
+    /// At least one.
+    Any,
+    Exactly(usize),
+    AtLeast(usize),
+}
+
+impl FromStr for Neighbours {
+    type Err = String;
+
+    /// "any", "2" for exactly two or "2+" for at least two.
+    fn from_str(s: &str) -> Result<Neighbours, String> {
+        let err = || "expected a count like 2, 2+ or any".to_string();
+        if s == "any" {
+            return Ok(Neighbours::Any);
+        }
+        match s.strip_suffix('+') {
+            Some(count) => count.parse().map(Neighbours::AtLeast).map_err(|_| err()),
+            None => s.parse().map(Neighbours::Exactly).map_err(|_| err()),
+        }
+    }
+}
+
+impl fmt::Display for Neighbours {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Neighbours::Any => write!(f, "any"),
+            Neighbours::Exactly(count) => write!(f, "{}", count),
+            Neighbours::AtLeast(count) => write!(f, "{}+", count),
+        }
+    }
+}
+
+impl Neighbours {
+    fn allows(&self, count: usize) -> bool {
+        match self {
+            Neighbours::Any => count > 0,
+            Neighbours::Exactly(n) => count == *n,
+            Neighbours::AtLeast(n) => count >= *n,
+        }
+    }
+}
+
+/// What a symbol is worth, e.g. "* 2 product" for the gears of part 2.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Rule {
+    /// The symbol, or None for every symbol.
+    pub symbol: Option<char>,
+    pub neighbours: Neighbours,
+    pub aggregate: Aggregate,
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self.symbol {
+            Some('#') => write!(f, "'#'")?,
+            Some(c) => write!(f, "{}", c)?,
+            None => write!(f, "any")?,
+        }
+        write!(f, " {} {}", self.neighbours, self.aggregate)
+    }
+}
+
+/// Rules for the symbols of a schematic. A symbol follows the first rule that matches it
+/// and the count of numbers next to it, and symbols without a rule are worth nothing.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Rules {
+    rules: Vec<Rule>,
+}
+
+impl Rules {
+    pub fn new(rules: Vec<Rule>) -> Rules {
+        Rules { rules }
+    }
+
+    /// The rule of part 2: a '*' next to exactly two numbers is a gear, worth their product.
+    pub fn gears() -> Rules {
+        Rules::new(vec![Rule {
+            symbol: Some('*'),
+            neighbours: Neighbours::Exactly(2),
+            aggregate: Aggregate::Product,
+        }])
+    }
+
+    /// Parse rules, one per line, as "SYMBOL NEIGHBOURS AGGREGATE":
+    ///
+    /// ```text
+    /// # Gears, and every other symbol worth its largest number
+    /// * 2 product
+    /// any any max
+    /// ```
+    ///
+    /// SYMBOL is a character, which can be quoted like '#', or "any". Lines starting
+    /// with '#' followed by a space are comments, unless the rest reads as a rule with
+    /// an unquoted '#', which is an error.
+    pub fn parse(lines: &Lines) -> Result<Rules, ParseError> {
+        let mut rules: Vec<Rule> = Vec::new();
+
+        for (i, line) in lines.iter().enumerate() {
+            let parts: Vec<&str> = line.split_whitespace().collect();
+            if line.starts_with("# ") || line == "#" {
+                if parts.len() == 3 && parts[1].parse::<Neighbours>().is_ok() && parts[2].parse::<Aggregate>().is_ok() {
+                    return Err(lines.error(i, parts[0], "a rule for '#' needs it quoted, like '#'"));
+                }
+                continue;
+            }
+            if parts.len() != 3 {
+                return Err(lines.error(i, line, "expected a symbol, a count of numbers and an aggregate"));
+            }
+
+            let symbol: &str = parts[0];
+            let unquoted: &str = symbol
+                .strip_prefix('\'')
+                .and_then(|s| s.strip_suffix('\''))
+                .unwrap_or(symbol);
+            let mut chars = unquoted.chars();
+            let symbol: Option<char> = match (unquoted, chars.next(), chars.next()) {
+                ("any", _, _) => None,
+                (_, Some(c), None) if !c.is_ascii_digit() && c != '.' => Some(c),
+                _ => return Err(lines.error(i, symbol, "expected one symbol other than a digit or '.', or any")),
+            };
+
+            rules.push(Rule {
+                symbol,
+                neighbours: parts[1].parse().map_err(|err: String| lines.error(i, parts[1], err))?,
+                aggregate: parts[2].parse().map_err(|err: String| lines.error(i, parts[2], err))?,
+            });
+        }
+        Ok(Rules::new(rules))
+    }
+
+    pub fn rules(&self) -> &[Rule] {
+        &self.rules
+    }
+
+    /// Add the rules of other after these.
+    pub fn extend(&mut self, other: Rules) {
+        self.rules.extend(other.rules);
+    }
+
+    /// Index of the rule of symbol c with count numbers next to it.
+    pub fn rule_for(&self, c: char, count: usize) -> Option<usize> {
+        self.rules
+            .iter()
+            .position(|rule| rule.symbol.is_none_or(|symbol| symbol == c) && rule.neighbours.allows(count))
+    }
+
+    /// Rule that applies to symbol c next to numbers of values, with the value of the symbol.
+    pub fn symbol_value(&self, c: char, values: &[u64]) -> Option<(usize, u64)> {
+        let r = self.rule_for(c, values.len())?;
+        Some((r, self.rules[r].aggregate.apply(values.iter().copied())))
+    }
+
+    /// Rule that applies to symbol s of schematic, with the value of the symbol.
+    pub fn value(&self, schematic: &Schematic, s: usize) -> Option<(usize, u64)> {
+        let values: Vec<u64> = schematic.numbers_next_to(s).iter().map(|i| schematic.numbers[*i].value as u64).collect();
+        self.symbol_value(schematic.symbols[s].c, &values)
+    }
+
+    /// Sum of the values of the symbols following each rule.
+    pub fn totals(&self, schematic: &Schematic) -> Vec<u64> {
+        let mut res: Vec<u64> = vec![0; self.rules.len()];
+        for s in 0..schematic.symbols.len() {
+            if let Some((r, value)) = self.value(schematic, s) {
+                res[r] += value;
+            }
+        }
+        res
+    }
+
+    /// Sum of the values of all symbols.
+    pub fn total(&self, schematic: &Schematic) -> u64 {
+        self.totals(schematic).iter().sum()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day03;
+    use aoc_common::{parse_example, Normalize};
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    fn rules(text: &str) -> Result<Rules, ParseError> {
+        Rules::parse(&Lines::new("rules", text, Normalize::TRIMMED))
+    }
+
+    #[test]
+    fn parse_and_display() {
+        let text = "# comment\n* 2 product\n'#' 1+ max\nany any sum";
+        let parsed = rules(text).unwrap();
+        let shown: Vec<String> = parsed.rules().iter().map(|rule| rule.to_string()).collect();
+        assert_eq!(shown, vec!["* 2 product", "'#' 1+ max", "any any sum"]);
+    }
+
+    #[test]
+    fn parse_errors_point_at_the_token() {
+        let err = rules("* two product").err().unwrap();
+        assert_eq!((err.column, err.text.as_str()), (3, "two"));
+        let err = rules("* 2 mean").err().unwrap();
+        assert_eq!(err.column, 5);
+        let err = rules("12 2 sum").err().unwrap();
+        assert_eq!(err.column, 1);
+    }
+
+    #[test]
+    fn unquoted_hash_rule_is_not_a_comment() {
+        assert_eq!(rules("# gears only\n# 2 sum, quoted").unwrap().rules().len(), 0);
+        let err = rules("* 2 product\n# 2 sum").err().unwrap();
+        assert_eq!((err.line, err.column), (2, 1));
+    }
+
+    #[test]
+    fn first_matching_rule_wins() {
+        let schematic = parse_example::<Day03>(EXAMPLE);
+        let gears = Rules::gears();
+        assert_eq!(gears.total(&schematic), 467835);
+
+        // The '*' next to only 617 falls through to the second rule.
+        let parsed = rules("* 2 product\nany any max").unwrap();
+        assert_eq!(parsed.totals(&schematic), vec![467835, 633 + 617 + 592 + 664]);
+    }
+}
@@ -0,0 +1,287 @@
+use std::fmt;
+use std::str::FromStr;
+
+/// Number in the schematic. Numbers never span rows.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Number {
+    pub value: u32,
+    pub row: usize,
+    /// Column of the first digit.
+    pub start: usize,
+    /// Column after the last digit.
+    pub end: usize,
+}
+
+/// Anything in the schematic other than a digit or '.'.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Symbol {
+    pub c: char,
+    pub row: usize,
+    pub column: usize,
+}
+
+/// Which cells around a symbol are next to it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Neighbourhood {
+    /// Left, right, above and below.
+    Orthogonal,
+    /// Every cell at most this many rows and columns away, so 1 includes the diagonals.
+    Radius(usize),
+}
+
+impl Neighbourhood {
+    /// The eight cells around a symbol, as in the puzzle.
+    pub const FULL: Neighbourhood = Neighbourhood::Radius(1);
+
+    /// Rows above and below a symbol the neighbourhood reaches.
+    fn reach(&self) -> usize {
+        match self {
+            Neighbourhood::Orthogonal => 1,
+            Neighbourhood::Radius(radius) => *radius,
+        }
+    }
+
+    pub(crate) fn touches(&self, symbol: &Symbol, number: &Number) -> bool {
+        let rows = symbol.row.abs_diff(number.row);
+        match self {
+            Neighbourhood::Orthogonal if rows == 0 => number.end == symbol.column || number.start == symbol.column + 1,
+            Neighbourhood::Orthogonal => rows == 1 && number.start <= symbol.column && symbol.column < number.end,
+            Neighbourhood::Radius(radius) => {
+                rows <= *radius
+                    && number.start <= symbol.column.saturating_add(*radius)
+                    && symbol.column < number.end.saturating_add(*radius)
+            }
+        }
+    }
+}
+
+impl FromStr for Neighbourhood {
+    type Err = String;
+
+    /// "orthogonal", "full" or a radius of at least 1.
+    fn from_str(s: &str) -> Result<Neighbourhood, String> {
+        match s {
+            "orthogonal" => Ok(Neighbourhood::Orthogonal),
+            "full" => Ok(Neighbourhood::FULL),
+            _ => match s.parse() {
+                Ok(radius) if radius > 0 => Ok(Neighbourhood::Radius(radius)),
+                _ => Err(format!("Unknown neighbourhood {}, expected orthogonal, full or a radius of 1 or more", s)),
+            },
+        }
+    }
+}
+
+impl fmt::Display for Neighbourhood {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            Neighbourhood::Orthogonal => write!(f, "orthogonal"),
+            Neighbourhood::Radius(1) => write!(f, "full"),
+            Neighbourhood::Radius(radius) => write!(f, "{}", radius),
+        }
+    }
+}
+
+/// How often a part number adds to the sum of part 1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Counting {
+    /// Once, however many symbols it is next to.
+    Once,
+    /// Once for every symbol it is next to.
+    PerSymbol,
+}
+
+impl FromStr for Counting {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Counting, String> {
+        match s {
+            "once" => Ok(Counting::Once),
+            "per-symbol" => Ok(Counting::PerSymbol),
+            _ => Err(format!("Unknown counting {}, expected once or per-symbol", s)),
+        }
+    }
+}
+
+/// Engine schematic: its numbers and symbols, and which of them are next to each other.
+/// Numbers and symbols are in reading order, and referred to by their index.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Schematic {
+    pub neighbourhood: Neighbourhood,
+    pub width: usize,
+    pub height: usize,
+    pub numbers: Vec<Number>,
+    pub symbols: Vec<Symbol>,
+    /// Numbers next to each symbol.
+    symbol_numbers: Vec<Vec<usize>>,
+    /// Symbols next to each number.
+    number_symbols: Vec<Vec<usize>>,
+}
+
+impl Schematic {
+    /// Schematic of width columns and height rows, working out which numbers and symbols
+    /// touch, diagonals included.
+    pub fn new(width: usize, height: usize, numbers: Vec<Number>, symbols: Vec<Symbol>) -> Schematic {
+        Schematic::with_neighbourhood(width, height, numbers, symbols, Neighbourhood::FULL)
+    }
+
+    /// Schematic where numbers and symbols touch within neighbourhood.
+    pub fn with_neighbourhood(
+        width: usize,
+        height: usize,
+        numbers: Vec<Number>,
+        symbols: Vec<Symbol>,
+        neighbourhood: Neighbourhood,
+    ) -> Schematic {
+        // Numbers of every row, so a symbol only looks at the rows around it.
+        let mut rows: Vec<Vec<usize>> = vec![Vec::new(); height];
+        for (i, number) in numbers.iter().enumerate() {
+            rows[number.row].push(i);
+        }
+
+        let mut symbol_numbers: Vec<Vec<usize>> = Vec::new();
+        let mut number_symbols: Vec<Vec<usize>> = vec![Vec::new(); numbers.len()];
+        for (s, symbol) in symbols.iter().enumerate() {
+            let mut next_to: Vec<usize> = Vec::new();
+            let reach = neighbourhood.reach();
+            let first_row = symbol.row.saturating_sub(reach);
+            for row in rows.iter().take(symbol.row.saturating_add(reach).saturating_add(1)).skip(first_row) {
+                for i in row.iter() {
+                    if neighbourhood.touches(symbol, &numbers[*i]) {
+                        next_to.push(*i);
+                        number_symbols[*i].push(s);
+                    }
+                }
+            }
+            next_to.sort();
+            symbol_numbers.push(next_to);
+        }
+
+        Schematic {
+            neighbourhood,
+            width,
+            height,
+            numbers,
+            symbols,
+            symbol_numbers,
+            number_symbols,
+        }
+    }
+
+    /// Indexes of the numbers next to symbol s.
+    pub fn numbers_next_to(&self, s: usize) -> &[usize] {
+        &self.symbol_numbers[s]
+    }
+
+    /// Indexes of the symbols next to number i.
+    pub fn symbols_next_to(&self, i: usize) -> &[usize] {
+        &self.number_symbols[i]
+    }
+
+    /// Numbers next to at least one symbol.
+    pub fn part_numbers(&self) -> impl Iterator<Item = &Number> {
+        self.numbers
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !self.number_symbols[*i].is_empty())
+            .map(|(_, number)| number)
+    }
+
+    /// Sum of the part numbers, counted once each or once per symbol next to them.
+    pub fn part_sum(&self, counting: Counting) -> u64 {
+        self.numbers
+            .iter()
+            .zip(self.number_symbols.iter())
+            .map(|(number, next_to)| {
+                let times = match counting {
+                    Counting::Once => next_to.len().min(1),
+                    Counting::PerSymbol => next_to.len(),
+                };
+                number.value as u64 * times as u64
+            })
+            .sum()
+    }
+
+    /// Indexes of the numbers next to more than one symbol.
+    pub fn shared_numbers(&self) -> impl Iterator<Item = usize> + '_ {
+        (0..self.numbers.len()).filter(|i| self.number_symbols[*i].len() > 1)
+    }
+
+    /// The same numbers and symbols, touching within neighbourhood instead.
+    pub fn with_neighbourhood_of(self, neighbourhood: Neighbourhood) -> Schematic {
+        Schematic::with_neighbourhood(self.width, self.height, self.numbers, self.symbols, neighbourhood)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn number(value: u32, row: usize, start: usize) -> Number {
+        let end = start + value.to_string().len();
+        Number { value, row, start, end }
+    }
+
+    #[test]
+    fn diagonals_touch() {
+        // 1.2
+        // .*.
+        // 3..4
+        let numbers = vec![number(1, 0, 0), number(2, 0, 2), number(3, 2, 0), number(4, 2, 3)];
+        let symbols = vec![Symbol { c: '*', row: 1, column: 1 }];
+        let schematic = Schematic::new(4, 3, numbers, symbols);
+        assert_eq!(schematic.numbers_next_to(0), &[0, 1, 2]);
+        assert_eq!(schematic.symbols_next_to(3), &[] as &[usize]);
+    }
+
+    #[test]
+    fn number_next_to_two_symbols() {
+        // #12*
+        let schematic = Schematic::new(
+            4,
+            1,
+            vec![number(12, 0, 1)],
+            vec![Symbol { c: '#', row: 0, column: 0 }, Symbol { c: '*', row: 0, column: 3 }],
+        );
+        assert_eq!(schematic.symbols_next_to(0), &[0, 1]);
+        assert_eq!(schematic.part_numbers().count(), 1);
+        assert_eq!(schematic.part_sum(Counting::Once), 12);
+        assert_eq!(schematic.part_sum(Counting::PerSymbol), 24);
+        assert_eq!(schematic.shared_numbers().collect::<Vec<usize>>(), vec![0]);
+    }
+
+    #[test]
+    fn neighbourhoods() {
+        // 1.2..
+        // .*...
+        // 3..45
+        // .....
+        // ...6.
+        let numbers = vec![number(1, 0, 0), number(2, 0, 2), number(3, 2, 0), number(45, 2, 3), number(6, 4, 3)];
+        let symbols = vec![Symbol { c: '*', row: 1, column: 1 }];
+        let touching = |neighbourhood| {
+            Schematic::with_neighbourhood(5, 5, numbers.clone(), symbols.clone(), neighbourhood)
+                .numbers_next_to(0)
+                .to_vec()
+        };
+        assert_eq!(touching(Neighbourhood::Orthogonal), Vec::<usize>::new());
+        assert_eq!(touching(Neighbourhood::FULL), vec![0, 1, 2]);
+        assert_eq!(touching(Neighbourhood::Radius(2)), vec![0, 1, 2, 3]);
+        assert_eq!(touching(Neighbourhood::Radius(3)), vec![0, 1, 2, 3, 4]);
+        assert_eq!(touching(Neighbourhood::Radius(usize::MAX)), vec![0, 1, 2, 3, 4]);
+
+        // .7.
+        // 8*9
+        let numbers = vec![number(7, 0, 1), number(8, 1, 0), number(9, 1, 2)];
+        let symbols = vec![Symbol { c: '*', row: 1, column: 1 }];
+        let schematic = Schematic::with_neighbourhood(3, 2, numbers, symbols, Neighbourhood::Orthogonal);
+        assert_eq!(schematic.numbers_next_to(0), &[0, 1, 2]);
+    }
+
+    #[test]
+    fn parse_neighbourhood() {
+        assert_eq!("orthogonal".parse(), Ok(Neighbourhood::Orthogonal));
+        assert_eq!("full".parse(), Ok(Neighbourhood::FULL));
+        assert_eq!("3".parse(), Ok(Neighbourhood::Radius(3)));
+        assert!("0".parse::<Neighbourhood>().is_err());
+    }
+}
@@ -0,0 +1,230 @@
+//! Both parts of the puzzle in one pass over a schematic of any length.
+//!
+//! Symbols only touch numbers on their own row and the rows above and below, so rows
+//! are read one at a time into a window of three. The symbols of the middle row are
+//! matched against the whole window, after which the first row can touch nothing more:
+//! its part numbers are added up and it is dropped.
+
+use crate::{parse_row, Neighbourhood, Number, Rules, Symbol};
+use aoc_common::{Lines, Normalize, ParseError};
+use std::collections::VecDeque;
+use std::io::{BufRead, BufReader, Read};
+
+/// Answers of both parts.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct SchematicSums {
+    /// Sum of the part numbers.
+    pub parts: u64,
+    /// Sum of the gear ratios.
+    pub gears: u64,
+}
+
+/// Numbers and symbols of one row, and which of the numbers are part numbers so far.
+#[derive(Default)]
+struct Row {
+    numbers: Vec<Number>,
+    symbols: Vec<Symbol>,
+    part: Vec<bool>,
+}
+
+struct Window {
+    /// The row above, the middle row and the row below. Rows before the first and after
+    /// the last are empty.
+    rows: VecDeque<Row>,
+    gears: Rules,
+    sums: SchematicSums,
+}
+
+impl Window {
+    fn new() -> Window {
+        Window {
+            rows: VecDeque::from([Row::default()]),
+            gears: Rules::gears(),
+            sums: SchematicSums::default(),
+        }
+    }
+
+    fn push(&mut self, row: Row) {
+        self.rows.push_back(row);
+        if self.rows.len() < 3 {
+            return;
+        }
+
+        for s in 0..self.rows[1].symbols.len() {
+            let symbol = self.rows[1].symbols[s];
+            let mut values: Vec<u64> = Vec::new();
+            for row in self.rows.iter_mut() {
+                for (number, part) in row.numbers.iter().zip(row.part.iter_mut()) {
+                    if Neighbourhood::FULL.touches(&symbol, number) {
+                        values.push(number.value as u64);
+                        *part = true;
+                    }
+                }
+            }
+            if let Some((_, ratio)) = self.gears.symbol_value(symbol.c, &values) {
+                self.sums.gears += ratio;
+            }
+        }
+
+        let done = self.rows.pop_front().unwrap();
+        self.add_parts(&done);
+    }
+
+    fn add_parts(&mut self, row: &Row) {
+        for (number, part) in row.numbers.iter().zip(row.part.iter()) {
+            if *part {
+                self.sums.parts += number.value as u64;
+            }
+        }
+    }
+
+    fn finish(mut self) -> SchematicSums {
+        self.push(Row::default());
+        for row in std::mem::take(&mut self.rows).iter() {
+            self.add_parts(row);
+        }
+        self.sums
+    }
+}
+
+/// Sums of both parts of the schematic read from reader, keeping three rows in memory.
+/// Blank lines are skipped, and errors are reported like those of the in-memory solver.
+pub fn stream_schematic_sums(reader: impl Read, name: &str) -> Result<SchematicSums, String> {
+    let mut window = Window::new();
+    let mut width: Option<usize> = None;
+    let mut row: usize = 0;
+
+    for (i, line) in BufReader::new(reader).lines().enumerate() {
+        let line = line.map_err(|err| format!("Cannot read {}: {}", name, err))?;
+        let lines = Lines::new(name, &line, Normalize::TRIMMED);
+        if lines.is_empty() {
+            continue;
+        }
+
+        let mut numbers: Vec<Number> = Vec::new();
+        let mut symbols: Vec<Symbol> = Vec::new();
+        // Errors point at line 1 of lines, which is line i + 1 of the input.
+        let located = |mut err: ParseError| {
+            err.line = i + 1;
+            err.to_string()
+        };
+        let row_width = parse_row(&lines, 0, &mut numbers, &mut symbols).map_err(located)?;
+        match width {
+            Some(width) if width != row_width => {
+                let message = format!("row is {} wide, expected {}", row_width, width);
+                return Err(located(lines.error(0, &lines[0], message)));
+            }
+            _ => width = Some(row_width),
+        }
+
+        for number in numbers.iter_mut() {
+            number.row = row;
+        }
+        for symbol in symbols.iter_mut() {
+            symbol.row = row;
+        }
+        let part = vec![false; numbers.len()];
+        window.push(Row { numbers, symbols, part });
+        row += 1;
+    }
+    Ok(window.finish())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Day03;
+    use aoc_common::{Answer, Solution};
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    /// Sums the way the in-memory solver computes them.
+    fn in_memory(text: &str) -> SchematicSums {
+        let schematic = Day03::parse(&Lines::new("test", text, Day03::NORMALIZE)).unwrap();
+        let number = |answer: Answer| match answer {
+            Answer::Number(n) => n as u64,
+            other => panic!("expected a number, got {:?}", other),
+        };
+        SchematicSums {
+            parts: number(Day03::part1(&schematic)),
+            gears: number(Day03::part2(&schematic)),
+        }
+    }
+
+    /// Random schematic of rows and width, from a linear congruential generator.
+    fn generate(rows: usize, width: usize, mut seed: u64) -> String {
+        let mut random = move |n: u64| {
+            seed = seed.wrapping_mul(6364136223846793005).wrapping_add(1442695040888963407);
+            (seed >> 33) % n
+        };
+        let mut res = String::with_capacity(rows * (width + 1));
+        for _ in 0..rows {
+            let mut column = 0;
+            while column < width {
+                match random(10) {
+                    0..=4 => res.push('.'),
+                    5..=7 => {
+                        // A number of up to three digits, cut off at the end of the row.
+                        let len = (1 + random(3) as usize).min(width - column);
+                        res.push(char::from(b'1' + random(9) as u8));
+                        for _ in 1..len {
+                            res.push(char::from(b'0' + random(10) as u8));
+                        }
+                        column += len;
+                        if column < width {
+                            res.push('.');
+                            column += 1;
+                        }
+                        continue;
+                    }
+                    8 => res.push('*'),
+                    _ => res.push(['#', '+', '$', '/', '@'][random(5) as usize]),
+                }
+                column += 1;
+            }
+            res.push('\n');
+        }
+        res
+    }
+
+    #[test]
+    fn example() {
+        let sums = stream_schematic_sums(EXAMPLE.as_bytes(), "example").unwrap();
+        assert_eq!(sums, SchematicSums { parts: 4361, gears: 467835 });
+    }
+
+    #[test]
+    fn short_schematics() {
+        for text in ["", "\n\n", "*", "12*", "1\n*\n2", "1.1\n.*.\n", "\n2*3\n\n4..\n"] {
+            assert_eq!(stream_schematic_sums(text.as_bytes(), "test").unwrap(), in_memory(text), "{:?}", text);
+        }
+    }
+
+    #[test]
+    fn errors_point_at_the_line() {
+        let err = stream_schematic_sums("1..\n\n.*.\n..\n".as_bytes(), "test").err().unwrap();
+        assert!(err.contains("row is 2 wide, expected 3"));
+        assert!(err.contains("test:4:1"));
+    }
+
+    fn same_as_in_memory_solver(rows: usize) {
+        for (width, seed) in [(12, 3), (40, 7)] {
+            let text = generate(rows, width, seed);
+            let sums = stream_schematic_sums(text.as_bytes(), "generated").unwrap();
+            assert_eq!(sums, in_memory(&text));
+            assert!(sums.gears > 0);
+        }
+    }
+
+    #[test]
+    fn same_as_in_memory_solver_on_generated_schematics() {
+        same_as_in_memory_solver(20_000);
+    }
+
+    /// Takes a few seconds in release mode: `cargo test --release -p day03 -- --ignored`.
+    #[test]
+    #[ignore]
+    fn same_as_in_memory_solver_on_millions_of_rows() {
+        same_as_in_memory_solver(2_000_000);
+    }
+}
@@ -0,0 +1,140 @@
+//! Day 4: Scratchcards
+
+use aoc_common::{Answer, Lines, Normalize, ParseError, Solution};
+use std::cmp::min;
+
+fn get_winning(lines: &Lines, i: usize) -> Result<(Vec<u32>, Vec<u32>), ParseError> {
+    let input: &str = &lines[i];
+    let (_, numbers) = input
+        .split_once(':')
+        .ok_or_else(|| lines.error(i, input, "expected 'Card N:' before the numbers"))?;
+    let (winning, values) = numbers
+        .split_once('|')
+        .ok_or_else(|| lines.error(i, numbers, "expected '|' between the numbers"))?;
+
+    let winning: Vec<u32> = lines.parse_numbers(i, winning)?;
+    let values: Vec<u32> = lines.parse_numbers(i, values)?;
+
+    Ok((winning, values))
+}
+
+/// Count how many winning numbers are in values.
+fn calculate_result(winning: &[u32], values: &[u32]) -> u32 {
+    let mut winning_values_count = 0;
+
+    for value in values.iter() {
+        if winning.contains(value) {
+            winning_values_count += 1;
+        }
+    }
+
+    winning_values_count
+}
+
+/// Calculate the score of the card.
+fn calculate_pow(winning: &[u32], values: &[u32]) -> u32 {
+    let winning_values_count = calculate_result(winning, values);
+
+    if winning_values_count <= 2 {
+        return winning_values_count;
+    }
+
+    2u32.pow(winning_values_count - 1)
+}
+
+fn get_lim(i: usize, won_count: u32, len: usize) -> usize {
+    min(1 + i + won_count as usize, len)
+}
+
+/// Winning numbers and the numbers we have, one entry per card.
+pub struct Cards {
+    winning: Vec<Vec<u32>>,
+    numbers: Vec<Vec<u32>>,
+}
+
+/// Split every card into its winning numbers and the numbers we have.
+fn parse_cards(input: &Lines) -> Result<Cards, ParseError> {
+    let mut winning: Vec<Vec<u32>> = Vec::new();
+    let mut numbers: Vec<Vec<u32>> = Vec::new();
+
+    for i in 0..input.len() {
+        let game: (Vec<u32>, Vec<u32>) = get_winning(input, i)?;
+        winning.push(game.0);
+        numbers.push(game.1);
+    }
+    Ok(Cards { winning, numbers })
+}
+
+pub struct Day04;
+
+impl Solution for Day04 {
+    const DAY: u8 = 4;
+    /// One card per line, blank lines dropped.
+    const NORMALIZE: Normalize = Normalize::TRIMMED;
+
+    type Input = Cards;
+
+    fn parse(input: &Lines) -> Result<Cards, ParseError> {
+        parse_cards(input)
+    }
+
+    /// Total score of all the cards.
+    fn part1(cards: &Cards) -> Answer {
+        let mut pow_sum: u32 = 0;
+
+        for i in 0..cards.winning.len() {
+            pow_sum += calculate_pow(&cards.winning[i], &cards.numbers[i]);
+        }
+        pow_sum.into()
+    }
+
+    /// Total count of scratchcards, including all the copies won.
+    fn part2(cards: &Cards) -> Answer {
+        let mut card_count: u32 = 0;
+        let mut cards_won: Vec<u32> = Vec::new();
+
+        cards_won.resize(cards.winning.len(), 1);
+
+        for i in 0..cards.winning.len() {
+            let won_count: u32 = calculate_result(&cards.winning[i], &cards.numbers[i]);
+
+            let lim: usize = get_lim(i, won_count, cards.winning.len());
+
+            for j in i + 1..lim {
+                cards_won[j] += cards_won[i];
+            }
+        }
+
+        for count in cards_won.iter() {
+            card_count += count;
+        }
+        card_count.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse_example;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = parse_example::<Day04>(EXAMPLE);
+        assert_eq!(Day04::part1(&input), Answer::Number(13));
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = parse_example::<Day04>(EXAMPLE);
+        assert_eq!(Day04::part2(&input), Answer::Number(30));
+    }
+
+    #[test]
+    fn calculate_pow_doubles_per_match() {
+        assert_eq!(calculate_pow(&[41, 48, 83, 86, 17], &[83, 86, 6, 31, 17, 9, 48, 53]), 8);
+        assert_eq!(calculate_pow(&[1, 2], &[2]), 1);
+        assert_eq!(calculate_pow(&[1, 2], &[3]), 0);
+    }
+}
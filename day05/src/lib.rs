@@ -0,0 +1,508 @@
+//! Day 5: If You Give A Seed A Fertilizer
+
+use aoc_common::{Answer, Lines, Normalize, ParseError, Solution};
+use std::cmp::max;
+use std::cmp::min;
+use std::cmp::Ordering;
+
+// INPUT PARSES
+
+/// The numbers of the 'seeds:' lines, pairs of the start and length of a range of seeds.
+fn parse_seeds(input: &Lines) -> Result<Vec<u64>, ParseError> {
+    let mut seeds: Vec<u64> = Vec::new();
+    let mut last_line: Option<usize> = None;
+    for (i, line) in input.iter().enumerate() {
+        if let Some(seedstring) = line.strip_prefix("seeds:") {
+            for text in seedstring.split_whitespace() {
+                let seed: u64 = input.parse(i, text)?;
+                if seeds.len() % 2 == 1 {
+                    let start = seeds[seeds.len() - 1];
+                    if seed == 0 {
+                        return Err(input.error(i, text, "a range of seeds cannot be empty"));
+                    }
+                    if start.checked_add(seed - 1).is_none() {
+                        return Err(input.error(i, text, "the range of seeds goes past the largest seed number"));
+                    }
+                }
+                seeds.push(seed);
+            }
+            last_line = Some(i);
+        }
+    }
+
+    match last_line {
+        None => Err(input.missing("expected a 'seeds:' line")),
+        Some(_) if seeds.is_empty() => Err(input.missing("expected seeds on the 'seeds:' line")),
+        Some(i) if seeds.len() % 2 == 1 => {
+            let message = format!("expected pairs of a start and a length, found {} numbers", seeds.len());
+            Err(input.error(i, &input[i], message))
+        }
+        Some(_) => Ok(seeds),
+    }
+}
+
+fn parse_map_name(line: &str) -> String {
+    line.split(' ').next().unwrap().to_string()
+}
+
+fn _print_seeds(input: &[u64]) {
+    print!("seeds:");
+    for i in input.iter() {
+        print!(" {}", i);
+    }
+    println!();
+}
+
+fn _print_map(map: &MapEntry) {
+    println!("{} {} {}", map.source, map.destination, map.range);
+}
+
+fn _print_maps(input: &[ValueMap]) {
+    for map in input.iter() {
+        println!("\n{} map:", map.name);
+        for values in map.map_entry.iter() {
+            _print_map(values);
+        }
+    }
+}
+
+/// Parse line i of the input into a map entry, None if it is not one.
+fn parse_seed_map(input: &Lines, i: usize) -> Result<Option<MapEntry>, ParseError> {
+    let line: &str = &input[i];
+    if !line.starts_with(|c: char| c.is_ascii_digit()) {
+        return Ok(None);
+    }
+    let vals: Vec<u64> = input.parse_numbers(i, line)?;
+
+    if vals.len() != 3 {
+        return Err(input.error(
+            i,
+            line,
+            format!("expected 3 numbers in a map entry, found {}", vals.len()),
+        ));
+    }
+
+    Ok(Some(MapEntry {
+        destination: vals[0],
+        source: vals[1],
+        range: vals[2],
+    }))
+}
+
+
+fn get_seedmaps(input: &Lines) -> Result<Vec<ValueMap>, ParseError> {
+    let mut seedmaps: Vec<ValueMap> = Vec::new();
+
+    //_print_seeds(&seeds);
+    let mut current_map: Option<ValueMap> = None;
+
+
+    for (i, line) in input.iter().enumerate() {
+        if line.ends_with("map:") {
+            if let Some(map) = current_map {
+                seedmaps.push(map);
+            }
+            current_map = Some(ValueMap {
+                name: parse_map_name(line),
+                map_entry: Vec::new(),
+            });
+            continue;
+        }
+
+        match parse_seed_map(input, i)? {
+            Some(map) => match current_map.as_mut() {
+                Some(current) => current.map_entry.push(map),
+                None => return Err(input.error(i, line, "map entry before any 'map:' header")),
+            },
+            None if line.starts_with("seeds:") => {}
+            None => return Err(input.error(i, line, "expected a map header or map entry")),
+        }
+    }
+
+    match current_map {
+        Some(map) => seedmaps.push(map),
+        None => return Err(input.missing("expected at least one map")),
+    }
+    Ok(seedmaps)
+
+}
+
+// STRUCTURES
+
+// Map format is: Source Destination Range (S, D, R)
+struct MapEntry {
+    source: u64,
+    destination: u64,
+    range: u64,
+}
+
+impl MapEntry {
+    fn source_end(&self) -> u64 {
+        self.source + self.range
+    }
+    fn effect_range(&self) -> ValueRange {
+        ValueRange::new(self.source, self.source_end())
+    }
+}
+
+struct ValueMap {
+    #[allow(dead_code)] // Only read by _print_maps when debugging.
+    name: String,
+    map_entry: Vec<MapEntry>,
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct ValueRange {
+    start: u64,
+    end: u64,
+}
+
+impl ValueRange {
+    fn new(start: u64, end: u64) -> ValueRange {
+        assert!(start <= end);
+        ValueRange {
+            start,
+            end,
+        }
+    }
+
+    fn overlaps(&self, other: &ValueRange) -> bool {
+        in_between(self.start, other.start, self.end)
+            || in_between(other.start, self.start, other.end)
+            || in_between(other.start, self.end, other.end)
+    }
+
+    fn merge(&mut self, other: &ValueRange) {
+        self.start = min(self.start, other.start);
+        self.end = max(self.end, other.end);
+    }
+    fn _print(&self) {
+        println!("[{} - {}]", self.start, self.end);
+    }
+    fn _to_string(&self) -> String {
+        format!("[{} - {}]", self.start, self.end).to_string()
+    }
+}
+
+impl PartialOrd for ValueRange {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for ValueRange {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.start.cmp(&other.start)
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct RangeCollection {
+    ranges: Vec<ValueRange>,
+}
+
+impl RangeCollection {
+    fn sort(&mut self) {
+        self.ranges.sort();
+    }
+
+    fn add(&mut self, range: &ValueRange) {
+        if self.ranges.is_empty() {
+            self.ranges.push(range.clone());
+        } else {
+            let mut was_merged = false;
+            for mrg in self.ranges.iter_mut() {
+                if mrg.overlaps(range) {
+                    mrg.merge(range);
+                    was_merged = true;
+                }
+            }
+            if !was_merged {
+                self.ranges.push(range.clone());
+            }
+        }
+        self.sort();
+        self.merge_all();
+    }
+
+    fn addv(&mut self, ranges: &[ValueRange]) {
+        for range in ranges.iter() {
+            self.add(range);
+        }
+    }
+
+    fn new() -> RangeCollection {
+        RangeCollection { ranges: Vec::new() }
+    }
+    fn _from(val: &[ValueRange]) -> RangeCollection {
+        RangeCollection {
+            ranges: val.to_vec(),
+        }
+    }
+
+    fn merge_all(&mut self) {
+        let mut new_range: Vec<ValueRange> = Vec::new();
+        new_range.push(self.ranges[0].clone());
+        for mrg in self.ranges.iter() {
+            if new_range.last().unwrap().overlaps(mrg) {
+                new_range.last_mut().unwrap().merge(mrg);
+            } else {
+                new_range.push(mrg.clone());
+            }
+        }
+        self.ranges = new_range;
+    }
+
+    fn _print(&self) {
+        for range in self.ranges.iter() {
+            range._print();
+        }
+    }
+    fn min(&self) -> Option<u64> {
+        self.ranges.first().map(|range| range.start)
+    }
+}
+
+// ########### HELPER FUNCTIONS ##################
+
+/// Check value c is between s:e
+fn in_between(s: u64, c: u64, e: u64) -> bool {
+    (s <= c) && (e >= c)
+}
+
+/// Checks that value is within source + range
+fn check_in_source_range(value: u64, map_entry: &MapEntry) -> bool {
+    in_between(map_entry.source, value, map_entry.source_end())
+}
+
+// Destination X = V + D - S if S <= V <= S + R else V
+// If D > S: X >= V
+// If D < S: X <= V
+fn get_destination(value: u64, map_entry: &MapEntry) -> u64 {
+    //println!("Checking value {}: {}, {}, {}", value, map_entry.destination, map_entry.source, map_entry.range);
+    let mut res = value;
+    if check_in_source_range(value, map_entry) {
+        res = value + map_entry.destination - map_entry.source;
+    }
+    //println!("Destination of {} is {}", value, res);
+    res
+}
+
+// ############# PROBLEM 1 FUNCTIONS ###############
+
+fn get_next_value(value: u64, next_maps: &[MapEntry]) -> u64 {
+    let mut next_location = value;
+    for map_entry in next_maps.iter() {
+        next_location = get_destination(value, map_entry);
+        if next_location != value {
+            return next_location;
+        }
+    }
+
+    next_location
+}
+
+/// Go through maps, find the location number for seed.
+/// Iterateing over positions, then maps.
+fn get_seed_location(seed: u64, maps: &[ValueMap]) -> u64 {
+    let mut next_location = seed;
+
+    for map in maps.iter() {
+        next_location = get_next_value(next_location, &map.map_entry);
+    }
+    next_location
+}
+
+/// Iterates over
+/// seed in seeds {
+///     map in maps {
+///         map_line in map {
+///             next_location = destination from 1st map matching.
+///         }
+///     }
+/// }
+fn get_min_location(seeds: &[u64], seedmaps: &[ValueMap]) -> u64 {
+    let mut min_location = 0;
+
+    for seed in seeds.iter() {
+        let seed_location = get_seed_location(*seed, seedmaps);
+        if min_location == 0 {
+            min_location = seed_location;
+        }
+        min_location = min(min_location, seed_location);
+    }
+    min_location
+}
+
+// ############ PROBLEM 2 CODE ###################
+
+/// Get ranges of seeds
+fn parse_seed_ranges(seeds: &[u64]) -> RangeCollection {
+    let mut seed_ranges: RangeCollection = RangeCollection::new();
+    let mut start: u64 = 0;
+
+    let mut mega_seedmap_len = 0;
+
+    for (i, seed) in seeds.iter().enumerate() {
+        if i % 2 == 1 {
+            mega_seedmap_len += seed;
+            seed_ranges.add(&ValueRange::new(start, start + seed - 1));
+        } else {
+            start = *seed;
+        }
+    }
+    seed_ranges
+}
+
+fn gets_split(vr: &ValueRange, mapping: &MapEntry) -> bool {
+    mapping.effect_range().overlaps(vr)
+}
+
+/// Split range to multiple ranges, based on mapping_to_next
+fn split_range(vr: &ValueRange, map_entry: &MapEntry) -> Vec<ValueRange> {
+    let mut res: Vec<ValueRange> = Vec::new();
+    // Range before the mapping comes out unchanged:
+    if vr.start < map_entry.source {
+        res.push(ValueRange::new(vr.start, min(vr.end, map_entry.source - 1)))
+    }
+
+    res.push(ValueRange::new(
+        get_destination(max(vr.start, map_entry.source), map_entry),
+        get_destination(min(vr.end, map_entry.source_end()), map_entry),
+    ));
+
+    // Range after the mapping comes out unchanged
+    if vr.end > map_entry.source_end() {
+        res.push(ValueRange::new(map_entry.source_end() + 1, vr.end))
+    }
+
+    res
+}
+
+fn get_ranges(input: &RangeCollection, map: &ValueMap) -> RangeCollection {
+    let mut next_range: RangeCollection = RangeCollection::new();
+
+    for range in input.ranges.iter() {
+        let mut was_found: bool = false;
+        for map_entry in map.map_entry.iter() {
+            if gets_split(range, map_entry) {
+                next_range.addv(&split_range(range, map_entry));
+                was_found = true;
+                break;
+            }
+        }
+        if !was_found {
+            next_range.add(range);
+        }
+    }
+
+    next_range
+}
+
+
+// ############ SOLUTIONS ###################
+
+/// Seeds and the maps from seed to location.
+pub struct Almanac {
+    seeds: Vec<u64>,
+    seedmaps: Vec<ValueMap>,
+}
+
+pub struct Day05;
+
+impl Solution for Day05 {
+    const DAY: u8 = 5;
+    /// Blank lines between the maps are dropped, map headers end in "map:".
+    const NORMALIZE: Normalize = Normalize::TRIMMED;
+
+    type Input = Almanac;
+
+    fn parse(input: &Lines) -> Result<Almanac, ParseError> {
+        Ok(Almanac {
+            seeds: parse_seeds(input)?,
+            seedmaps: get_seedmaps(input)?,
+        })
+    }
+
+    /// Lowest location number of the listed seeds.
+    fn part1(almanac: &Almanac) -> Answer {
+        get_min_location(&almanac.seeds, &almanac.seedmaps).into()
+    }
+
+    /// Lowest location number when the seeds line lists ranges of seeds.
+    fn part2(almanac: &Almanac) -> Answer {
+        let seed_ranges: RangeCollection = parse_seed_ranges(&almanac.seeds);
+        let mut current_ranges: RangeCollection = seed_ranges.clone();
+
+        for map in almanac.seedmaps.iter() {
+            current_ranges = get_ranges(&current_ranges, map);
+        }
+        match current_ranges.min() {
+            Some(min) => min.into(),
+            None => Answer::Unsolved,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse_example;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = parse_example::<Day05>(EXAMPLE);
+        assert_eq!(Day05::part1(&input), Answer::Number(35));
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = parse_example::<Day05>(EXAMPLE);
+        assert_eq!(Day05::part2(&input), Answer::Number(46));
+    }
+
+    fn seed_to_soil() -> MapEntry {
+        // "52 50 48" in the example.
+        MapEntry {
+            destination: 52,
+            source: 50,
+            range: 48,
+        }
+    }
+
+    #[test]
+    fn split_range_inside_mapping_is_moved() {
+        let res = split_range(&ValueRange::new(79, 92), &seed_to_soil());
+        assert!(res == vec![ValueRange::new(81, 94)]);
+    }
+
+    #[test]
+    fn split_range_before_mapping_is_kept() {
+        let res = split_range(&ValueRange::new(40, 60), &seed_to_soil());
+        assert!(res == vec![ValueRange::new(40, 49), ValueRange::new(52, 62)]);
+    }
+
+    #[test]
+    fn seed_ranges_are_checked() {
+        let err = Day05::parse(&Lines::new("bad", "seeds: 0 0", Day05::NORMALIZE)).err().unwrap();
+        assert_eq!((err.line, err.column, err.message.as_str()), (1, 10, "a range of seeds cannot be empty"));
+        let err = Day05::parse(&Lines::new("bad", "seeds: 79", Day05::NORMALIZE)).err().unwrap();
+        assert_eq!((err.line, err.column), (1, 1));
+        let err = Day05::parse(&Lines::new("bad", "seeds: 18446744073709551615 2", Day05::NORMALIZE)).err().unwrap();
+        assert_eq!(err.column, 29);
+        assert!(Day05::parse(&Lines::new("bad", "seeds:", Day05::NORMALIZE)).is_err());
+    }
+
+    #[test]
+    fn seed_locations_of_example() {
+        let almanac = parse_example::<Day05>(EXAMPLE);
+        let locations: Vec<u64> = almanac
+            .seeds
+            .iter()
+            .map(|seed| get_seed_location(*seed, &almanac.seedmaps))
+            .collect();
+        assert_eq!(locations, vec![82, 43, 86, 35]);
+    }
+}
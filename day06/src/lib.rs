@@ -0,0 +1,125 @@
+//! Day 6: Wait For It
+
+use aoc_common::{Answer, Lines, Normalize, ParseError, Solution};
+
+/// Calculate all possible times that reach distance
+// x = t*v
+// v = tv
+// x = (t - v) * v
+// x = t*v - v*v
+fn press_times_for_distance(total_time: u64, distance: u64) -> u64 {
+    let mut press_times: u64 = 0;
+
+    for v in 1..total_time.saturating_sub(1) {
+        if (total_time - v) * v > distance {
+            press_times += 1;
+        }
+    }
+
+    press_times
+}
+
+/// Parse line i, e.g. "Time:      7  15   30", into the numbers and
+/// the one long number they form when the spaces are ignored.
+fn parse_row(input: &Lines, i: usize, label: &str) -> Result<(Vec<u64>, u64), ParseError> {
+    let line: &str = match input.get(i) {
+        Some(line) => line,
+        None => return Err(input.missing(format!("expected a '{}' line", label))),
+    };
+    let numbers: &str = line
+        .strip_prefix(label)
+        .ok_or_else(|| input.error(i, line, format!("expected the line to start with '{}'", label)))?;
+
+    let long_string: String = numbers.split_whitespace().collect();
+    let long: u64 = long_string.parse().map_err(|err| {
+        input.error(i, numbers.trim(), format!("cannot parse '{}': {}", long_string, err))
+    })?;
+
+    Ok((input.parse_numbers(i, numbers)?, long))
+}
+
+pub struct Races {
+    times: Vec<u64>,
+    distances: Vec<u64>,
+    long_time: u64,
+    long_dist: u64,
+}
+
+pub struct Day06;
+
+impl Solution for Day06 {
+    const DAY: u8 = 6;
+    /// Time and distance on the first two lines.
+    const NORMALIZE: Normalize = Normalize::TRIMMED;
+
+    type Input = Races;
+
+    fn parse(input: &Lines) -> Result<Races, ParseError> {
+        let (times, long_time) = parse_row(input, 0, "Time:")?;
+        let (distances, long_dist) = parse_row(input, 1, "Distance:")?;
+
+        if times.len() != distances.len() {
+            return Err(input.error(
+                1,
+                &input[1],
+                format!("expected {} distances, one per race", times.len()),
+            ));
+        }
+
+        Ok(Races {
+            times,
+            distances,
+            long_time,
+            long_dist,
+        })
+    }
+
+    /// Product of the ways to win each race.
+    fn part1(races: &Races) -> Answer {
+        let mut output: u64 = 1;
+
+        for i in 0..races.times.len() {
+            output *= press_times_for_distance(races.times[i], races.distances[i]);
+        }
+        output.into()
+    }
+
+    /// Ways to win the one long race.
+    fn part2(races: &Races) -> Answer {
+        press_times_for_distance(races.long_time, races.long_dist).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse_example;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = parse_example::<Day06>(EXAMPLE);
+        assert_eq!(Day06::part1(&input), Answer::Number(288));
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = parse_example::<Day06>(EXAMPLE);
+        assert_eq!(Day06::part2(&input), Answer::Number(71503));
+    }
+
+    #[test]
+    fn press_times_of_example_races() {
+        assert_eq!(press_times_for_distance(7, 9), 4);
+        assert_eq!(press_times_for_distance(15, 40), 8);
+        assert_eq!(press_times_for_distance(30, 200), 9);
+    }
+
+    #[test]
+    fn race_without_time_cannot_be_won() {
+        assert_eq!(press_times_for_distance(0, 0), 0);
+        let input = parse_example::<Day06>("Time: 0\nDistance: 0");
+        assert_eq!(Day06::part1(&input), Answer::Number(0));
+    }
+}
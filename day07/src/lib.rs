@@ -0,0 +1,349 @@
+//! Day 7: Camel Cards
+
+use aoc_common::{Answer, Lines, Normalize, ParseError, Solution};
+use std::cmp::Ordering;
+use std::collections::HashMap;
+use std::fmt;
+
+#[derive(Clone, PartialEq, Eq)]
+struct Card {
+    val: u32,
+    letter: char,
+}
+
+impl Card {
+    fn from_char(c: char) -> Option<Card> {
+        let val: u32;
+        if c.is_ascii_digit() {
+            val = c.to_string().parse().unwrap();
+        } else {
+            match c {
+                'T' => {
+                    val = 10;
+                }
+                'J' => {
+                    val = 11;
+                }
+                'Q' => {
+                    val = 12;
+                }
+                'K' => {
+                    val = 13;
+                }
+                'A' => {
+                    val = 14;
+                }
+                _ => {
+                    return None;
+                }
+            }
+        }
+
+        Some(Card {
+            val,
+            letter: c,
+        })
+    }
+
+    fn from_char_as_jokers(c: char) -> Option<Card> {
+        let val: u32;
+        if c.is_ascii_digit() {
+            val = c.to_string().parse().unwrap();
+        } else {
+            match c {
+                'T' => {
+                    val = 10;
+                }
+                'J' => {
+                    val = 1;
+                }
+                'Q' => {
+                    val = 12;
+                }
+                'K' => {
+                    val = 13;
+                }
+                'A' => {
+                    val = 14;
+                }
+                _ => {
+                    return None;
+                }
+            }
+        }
+
+        Some(Card {
+            val,
+            letter: c,
+        })
+    }
+}
+
+#[derive(Clone, PartialEq, Eq)]
+struct Hand {
+    cards: Vec<Card>,
+    bid: u32,
+    joker_count: u32,
+    h_map: HashMap<u32, u32>,
+}
+
+fn to_hashmap(cards: &[Card], joker_count: u32) -> HashMap<u32, u32> {
+    let mut valmap: HashMap<u32, u32> = HashMap::new();
+    for card in cards.iter() {
+        let existing = valmap.get(&card.val);
+        if (joker_count > 0) && card.letter == 'J' {
+            continue;
+        }
+        if let Some(existing) = existing {
+            valmap.insert(card.val, existing + 1);
+        } else {
+            valmap.insert(card.val, 1);
+        }
+    }
+    valmap
+}
+
+/// Split line i into its cards and bid, e.g. "32T3K 765".
+fn split_hand(lines: &Lines, i: usize) -> Result<(&str, u32), ParseError> {
+    let line: &str = &lines[i];
+    let mut h_b = line.split_whitespace();
+
+    let (cards, bid) = match (h_b.next(), h_b.next(), h_b.next()) {
+        (Some(cards), Some(bid), None) => (cards, bid),
+        _ => return Err(lines.error(i, line, "expected five cards and a bid")),
+    };
+    if cards.chars().count() != 5 {
+        return Err(lines.error(i, cards, "expected five cards"));
+    }
+
+    Ok((cards, lines.parse(i, bid)?))
+}
+
+/// Cards of line i, read with `from_char`.
+fn parse_cards(
+    lines: &Lines,
+    i: usize,
+    from_char: fn(char) -> Option<Card>,
+) -> Result<(Vec<Card>, u32), ParseError> {
+    let (cards_str, bid) = split_hand(lines, i)?;
+    let mut cards: Vec<Card> = Vec::new();
+
+    for (j, c) in cards_str.char_indices() {
+        match from_char(c) {
+            Some(card) => cards.push(card),
+            None => {
+                let text = &cards_str[j..j + c.len_utf8()];
+                return Err(lines.error(i, text, format!("invalid card '{}'", c)));
+            }
+        }
+    }
+    Ok((cards, bid))
+}
+
+impl Hand {
+    fn from_string(lines: &Lines, i: usize) -> Result<Hand, ParseError> {
+        let (cards, bid) = parse_cards(lines, i, Card::from_char)?;
+        let hmap: HashMap<u32, u32> = to_hashmap(&cards, 0);
+
+        Ok(Hand {
+            cards,
+            bid,
+            joker_count: 0,
+            h_map: hmap,
+        })
+    }
+    fn from_string_as_jokers(lines: &Lines, i: usize) -> Result<Hand, ParseError> {
+        let (cards, bid) = parse_cards(lines, i, Card::from_char_as_jokers)?;
+        let mut joker_count = 0;
+
+        for card in cards.iter() {
+            if card.letter == 'J' {
+                joker_count += 1;
+            }
+        }
+
+        let h_map: HashMap<u32, u32> = to_hashmap(&cards, joker_count);
+
+        Ok(Hand {
+            cards,
+            bid,
+            joker_count,
+            h_map,
+        })
+    }
+
+    fn max_same(&self) -> u32 {
+        let mmap = &self.h_map;
+        let key = mmap.iter().max_by(|a, b| a.1.cmp(b.1)).map(|(k, _v)| k);
+        if key.is_none() {
+            return 0;
+        }
+
+        *mmap.get(key.unwrap()).unwrap()
+    }
+
+    fn _print(&self) {
+        println!("{}", self);
+    }
+}
+
+impl fmt::Display for Hand {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        for card in self.cards.iter() {
+            write!(f, "{}", card.letter)?;
+        }
+        write!(f, " {}", self.bid)
+    }
+}
+
+impl PartialOrd for Hand {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for Hand {
+    fn cmp(&self, other: &Hand) -> Ordering {
+        let same_s = self.max_same() + self.joker_count;
+        let same_o = other.max_same() + other.joker_count;
+
+        if same_s > same_o {
+            return Ordering::Greater;
+        }
+        if same_s < same_o {
+            return Ordering::Less;
+        }
+
+        if (same_s == 2) || (same_s == 3) {
+            let c_s = self.h_map.len();
+            let c_o = other.h_map.len();
+            if c_s != c_o {
+                return if c_s < c_o {
+                    Ordering::Greater
+                } else {
+                    Ordering::Less
+                };
+            }
+        }
+
+        for i in 0..self.cards.len() {
+            let sv = self.cards[i].val;
+            let ov = other.cards[i].val;
+            if sv > ov {
+                return Ordering::Greater;
+            }
+            if ov > sv {
+                return Ordering::Less;
+            }
+        }
+
+        Ordering::Equal
+    }
+}
+
+/// Total winnings of a set of hands.
+fn total_winnings(hands: &[Hand]) -> u32 {
+    let mut hands: Vec<Hand> = hands.to_vec();
+    hands.sort();
+
+    let mut score: u32 = 0;
+
+    for (i, hand) in hands.iter().enumerate() {
+        score += (i as u32 + 1) * hand.bid;
+    }
+    score
+}
+
+/// Every hand read twice: J as a jack and J as a joker.
+pub struct Hands {
+    jacks: Vec<Hand>,
+    jokers: Vec<Hand>,
+}
+
+pub struct Day07;
+
+impl Solution for Day07 {
+    const DAY: u8 = 7;
+    /// One hand and its bid per line.
+    const NORMALIZE: Normalize = Normalize::TRIMMED;
+
+    type Input = Hands;
+
+    fn parse(input: &Lines) -> Result<Hands, ParseError> {
+        let mut jacks: Vec<Hand> = Vec::new();
+        let mut jokers: Vec<Hand> = Vec::new();
+
+        for i in 0..input.len() {
+            jacks.push(Hand::from_string(input, i)?);
+            jokers.push(Hand::from_string_as_jokers(input, i)?);
+        }
+        Ok(Hands { jacks, jokers })
+    }
+
+    /// Total winnings with J as a jack.
+    fn part1(hands: &Hands) -> Answer {
+        total_winnings(&hands.jacks).into()
+    }
+
+    /// Total winnings with J as a joker.
+    fn part2(hands: &Hands) -> Answer {
+        total_winnings(&hands.jokers).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse_example;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = parse_example::<Day07>(EXAMPLE);
+        assert_eq!(Day07::part1(&input), Answer::Number(6440));
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = parse_example::<Day07>(EXAMPLE);
+        assert_eq!(Day07::part2(&input), Answer::Number(5905));
+    }
+
+    fn hand(line: &str) -> Hand {
+        Hand::from_string(&Lines::new("test", line, Day07::NORMALIZE), 0).unwrap()
+    }
+
+    fn joker_hand(line: &str) -> Hand {
+        Hand::from_string_as_jokers(&Lines::new("test", line, Day07::NORMALIZE), 0).unwrap()
+    }
+
+    #[test]
+    fn cmp_orders_by_type_first() {
+        assert_eq!(hand("22222 1").cmp(&hand("AAAAK 1")), Ordering::Greater);
+        assert_eq!(hand("2223K 1").cmp(&hand("AAKKQ 1")), Ordering::Greater);
+        assert_eq!(hand("22333 1").cmp(&hand("AAA23 1")), Ordering::Greater);
+        assert_eq!(hand("AKQJT 1").cmp(&hand("22345 1")), Ordering::Less);
+    }
+
+    #[test]
+    fn cmp_breaks_ties_card_by_card() {
+        assert_eq!(hand("33332 1").cmp(&hand("2AAAA 1")), Ordering::Greater);
+        assert_eq!(hand("KK677 1").cmp(&hand("KTJJT 1")), Ordering::Greater);
+        assert_eq!(hand("T55J5 1").cmp(&hand("T55J5 2")), Ordering::Equal);
+    }
+
+    #[test]
+    fn cmp_with_jokers() {
+        assert_eq!(joker_hand("KTJJT 1").cmp(&joker_hand("QQQJA 1")), Ordering::Greater);
+        assert_eq!(joker_hand("JKKK2 1").cmp(&joker_hand("QQQQ2 1")), Ordering::Less);
+        assert_eq!(joker_hand("JJJJJ 1").cmp(&joker_hand("2222A 1")), Ordering::Greater);
+    }
+
+    #[test]
+    fn invalid_card_is_an_error() {
+        let lines = Lines::new("bad", "32T3X 765", Day07::NORMALIZE);
+        let err = Day07::parse(&lines).err().unwrap();
+        assert_eq!((err.column, err.text.as_str()), (5, "X"));
+    }
+}
@@ -0,0 +1,220 @@
+//! Day 8: Haunted Wasteland
+
+use aoc_common::{Answer, Lines, Normalize, ParseError, Solution};
+use regex::Regex;
+use std::collections::HashMap;
+use num::integer::lcm;
+
+enum Direction {
+    Left,
+    Right,
+}
+
+fn c2dir(c: char) -> Direction {
+    if c == 'L' {
+        return Direction::Left;
+    }
+    Direction::Right
+}
+
+#[derive(Clone)]
+struct MapNode {
+    left: String,
+    right: String,
+    me: String,
+}
+
+impl MapNode {
+    fn new(left: String, right: String, me: String) -> MapNode {
+        MapNode {
+            left,
+            right,
+            me,
+        }
+    }
+
+    /// Parse a node line, e.g. "AAA = (BBB, CCC)".
+    fn from_line(re: &Regex, line: &str) -> Option<(String, MapNode)> {
+        let caps = re.captures(line)?;
+        let name = caps[1].to_string();
+
+        Some((
+            name.clone(),
+            MapNode::new(caps[2].to_string(), caps[3].to_string(), name),
+        ))
+    }
+
+    fn get_next(&self, dir: Direction) -> String {
+        match dir {
+            Direction::Left => self.left.clone(),
+            Direction::Right => {
+                self.right.clone()
+            }
+        }
+    }
+
+    fn navigate<'a>(&self, dir: Direction, map: &'a HashMap<String, MapNode>) -> &'a MapNode {
+        map.get(&self.get_next(dir)).unwrap()
+    }
+}
+
+/// Check the instructions on the first line are all L or R.
+fn parse_instructions(input: &Lines) -> Result<String, ParseError> {
+    let line: &str = match input.first() {
+        Some(line) => line,
+        None => return Err(input.missing("expected a line of L/R instructions")),
+    };
+
+    for (i, c) in line.char_indices() {
+        if c != 'L' && c != 'R' {
+            let text = &line[i..i + c.len_utf8()];
+            return Err(input.error(0, text, format!("invalid instruction '{}'", c)));
+        }
+    }
+    Ok(line.to_string())
+}
+
+/// Parse the node lines following the instructions.
+fn parse_map(input: &Lines) -> Result<HashMap<String, MapNode>, ParseError> {
+    let mut map: HashMap<String, MapNode> = HashMap::new();
+    let re: Regex = Regex::new(r"^(\w+)\s*=\s*\((\w+),\s*(\w+)\)$").unwrap();
+
+    for i in 1..input.len() {
+        let val = MapNode::from_line(&re, &input[i])
+            .ok_or_else(|| input.error(i, &input[i], "expected a node like 'AAA = (BBB, CCC)'"))?;
+        if map.contains_key(&val.0) {
+            let name: &str = &input[i][..val.0.len()];
+            return Err(input.error(i, name, format!("node {} is defined twice", val.0)));
+        }
+        map.insert(val.0, val.1);
+    }
+
+    // Every node the network points to has to exist.
+    for i in 1..input.len() {
+        let caps = re.captures(&input[i]).unwrap();
+        for next in [caps.get(2).unwrap(), caps.get(3).unwrap()] {
+            if !map.contains_key(next.as_str()) {
+                let text: &str = next.as_str();
+                return Err(input.error(i, text, format!("unknown node {}", text)));
+            }
+        }
+    }
+    Ok(map)
+}
+
+/// Left/right instructions and the nodes by name.
+pub struct Network {
+    instructions: String,
+    map: HashMap<String, MapNode>,
+}
+
+pub struct Day08;
+
+impl Solution for Day08 {
+    const DAY: u8 = 8;
+    /// Instructions on the first line, then one node per line.
+    const NORMALIZE: Normalize = Normalize::TRIMMED;
+
+    type Input = Network;
+
+    fn parse(input: &Lines) -> Result<Network, ParseError> {
+        Ok(Network {
+            instructions: parse_instructions(input)?,
+            map: parse_map(input)?,
+        })
+    }
+
+    /// Steps from AAA to ZZZ. Unsolved if there is no AAA, like in the example of part 2.
+    fn part1(network: &Network) -> Answer {
+        let map = &network.map;
+        let mut position: &MapNode = match map.get("AAA") {
+            Some(position) => position,
+            None => return Answer::Unsolved,
+        };
+
+        let mut steps: u64 = 0;
+
+        while position.left != position.me
+            && position.right != position.me
+            && position.me != "ZZZ"
+        {
+            for instruction in network.instructions.chars() {
+                position = position.navigate(c2dir(instruction), map);
+                steps += 1;
+            }
+        }
+        steps.into()
+    }
+
+    /// Steps until every ghost starting from a node ending in A is on a node ending in Z.
+    /// Unsolved if no node ends in A.
+    fn part2(network: &Network) -> Answer {
+        let map = &network.map;
+
+        let mut positions: Vec<&MapNode> = Vec::new();
+        for key in map.keys() {
+            if key.ends_with('A') {
+                positions.push(map.get(key).unwrap());
+            }
+        }
+
+        let mut results: Vec<u64> = Vec::new();
+
+        for position_start in positions.iter() {
+            let mut steps: u64 = 0;
+            let mut position = *position_start;
+            while !position.me.ends_with('Z') {
+                for instruction in network.instructions.chars() {
+                    position = position.navigate(c2dir(instruction), map);
+                    steps += 1;
+                }
+            }
+            results.push(steps);
+        }
+        let mut prev = match results.first() {
+            Some(first) => *first,
+            None => return Answer::Unsolved,
+        };
+
+        for val in results.iter() {
+            prev = lcm(prev, *val);
+        }
+        prev.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse_example;
+
+    const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+    const EXAMPLE2: &str = include_str!("../fixtures/example2.txt");
+    const EXAMPLE3: &str = include_str!("../fixtures/example3.txt");
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(Day08::part1(&parse_example::<Day08>(EXAMPLE1)), Answer::Number(2));
+        assert_eq!(Day08::part1(&parse_example::<Day08>(EXAMPLE2)), Answer::Number(6));
+    }
+
+    #[test]
+    fn part2_example() {
+        assert_eq!(Day08::part2(&parse_example::<Day08>(EXAMPLE3)), Answer::Number(6));
+    }
+
+    #[test]
+    fn missing_start_nodes_are_unsolved() {
+        assert_eq!(Day08::part1(&parse_example::<Day08>(EXAMPLE3)), Answer::Unsolved);
+        assert_eq!(Day08::part2(&parse_example::<Day08>("L
+
+BBB = (BBB, BBB)")), Answer::Unsolved);
+    }
+
+    #[test]
+    fn unknown_node_is_an_error() {
+        let lines = Lines::new("bad", "LR\n\nAAA = (BBB, ZZZ)\nZZZ = (ZZZ, ZZZ)", Day08::NORMALIZE);
+        let err = Day08::parse(&lines).err().unwrap();
+        assert_eq!((err.line, err.column), (3, 8));
+    }
+}
@@ -0,0 +1,137 @@
+//! Day 9: Mirage Maintenance
+
+use aoc_common::{Answer, Lines, Normalize, ParseError, Solution};
+
+/// Sum of the differences between the values of input, and the differences.
+/// A history of one value has no differences.
+fn next_value(input: &[i32]) -> (i32, Vec<i32>) {
+    let mut res: Vec<i32> = Vec::new();
+    if input.len() <= 1 {
+        return (0, res);
+    }
+
+    for (i, val) in input.iter().enumerate() {
+        res.push(input[i + 1] - val);
+
+        if i == input.len() - 2 {
+            break;
+        }
+    }
+    let mut sum = 0;
+
+    res.iter().for_each(|v| {
+        sum += v;
+    });
+
+    (sum, res)
+}
+
+fn get_last(input: &[Vec<i32>]) -> i32 {
+    let mut sum = 0;
+    input.iter().for_each(|v: &Vec<i32>| {
+        sum += v.last().unwrap();
+    });
+
+    sum
+}
+
+fn get_first(input: &[Vec<i32>]) -> i32 {
+    let mut last_new_first = 0;
+    input.iter().rev().for_each(|v: &Vec<i32>| {
+        last_new_first = v.first().unwrap() - last_new_first;
+    });
+
+    last_new_first
+}
+
+/// The history followed by its rows of differences, down to the all zero row,
+/// or to a single value when the differences never all become zero.
+fn get_all_outputs(history: &[i32]) -> Vec<Vec<i32>> {
+    let mut input: Vec<i32> = history.to_vec();
+    let mut all_outputs: Vec<Vec<i32>> = Vec::new();
+    all_outputs.push(input.clone());
+
+    while input.len() > 1 && input.iter().any(|v| *v != 0) {
+        input = next_value(&input).1;
+        all_outputs.push(input.clone());
+    }
+    all_outputs
+}
+
+pub struct Day09;
+
+impl Solution for Day09 {
+    const DAY: u8 = 9;
+    /// One history per line.
+    const NORMALIZE: Normalize = Normalize::TRIMMED;
+
+    type Input = Vec<Vec<i32>>;
+
+    fn parse(input: &Lines) -> Result<Vec<Vec<i32>>, ParseError> {
+        (0..input.len())
+            .map(|i| input.parse_numbers(i, &input[i]))
+            .collect()
+    }
+
+    /// Sum of the extrapolated next values.
+    fn part1(histories: &Vec<Vec<i32>>) -> Answer {
+        let mut total_sum: i32 = 0;
+
+        for v in histories.iter() {
+            total_sum += get_last(&get_all_outputs(v));
+        }
+        total_sum.into()
+    }
+
+    /// Sum of the extrapolated previous values.
+    fn part2(histories: &Vec<Vec<i32>>) -> Answer {
+        let mut total_first_sum: i32 = 0;
+
+        for v in histories.iter() {
+            total_first_sum += get_first(&get_all_outputs(v));
+        }
+        total_first_sum.into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse_example;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = parse_example::<Day09>(EXAMPLE);
+        assert_eq!(Day09::part1(&input), Answer::Number(114));
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = parse_example::<Day09>(EXAMPLE);
+        assert_eq!(Day09::part2(&input), Answer::Number(2));
+    }
+
+    #[test]
+    fn next_value_gives_sum_and_differences() {
+        assert_eq!(next_value(&[0, 3, 6, 9, 12, 15]), (15, vec![3, 3, 3, 3, 3]));
+        assert_eq!(next_value(&[3, 3, 3]), (0, vec![0, 0]));
+    }
+
+    #[test]
+    fn one_value_history_extrapolates_to_itself() {
+        let histories = vec![vec![7]];
+        assert_eq!(get_all_outputs(&histories[0]), vec![vec![7]]);
+        assert_eq!(Day09::part1(&histories), Answer::Number(7));
+        assert_eq!(Day09::part2(&histories), Answer::Number(7));
+    }
+
+    #[test]
+    fn differences_summing_to_zero_are_not_all_zero() {
+        // 1 2 1 has differences 1 -1, then -2: the next value is -2, the previous -2.
+        assert_eq!(get_all_outputs(&[1, 2, 1]), vec![vec![1, 2, 1], vec![1, -1], vec![-2]]);
+        assert_eq!(Day09::part1(&vec![vec![1, 2, 1]]), Answer::Number(-2));
+        assert_eq!(Day09::part2(&vec![vec![1, 2, 1]]), Answer::Number(-2));
+    }
+}
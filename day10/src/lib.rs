@@ -0,0 +1,337 @@
+//! Day 10: Pipe Maze
+
+use aoc_common::{Answer, Lines, Normalize, ParseError, Solution};
+
+struct Pos {
+    x: usize,
+    y: usize,
+    p_x: usize,
+    p_y: usize,
+}
+
+impl Pos {
+    fn new(x: usize, y: usize) -> Pos {
+        Pos {
+            x,
+            y,
+            p_x: x,
+            p_y: y,
+        }
+    }
+
+    fn update_prev_to_current(&mut self) {
+        self.p_x = self.x;
+        self.p_y = self.y;
+    }
+
+    // move left, off the grid to usize::MAX at the edge
+    fn m_l(&mut self) {
+        self.update_prev_to_current();
+        self.x = self.x.wrapping_sub(1);
+    }
+
+    // move right
+    fn m_r(&mut self) {
+        self.update_prev_to_current();
+        self.x += 1;
+    }
+
+    // move up, off the grid to usize::MAX at the edge
+    fn m_u(&mut self) {
+        self.update_prev_to_current();
+        self.y = self.y.wrapping_sub(1);
+    }
+
+    // move down
+    fn m_d(&mut self) {
+        self.update_prev_to_current();
+        self.y += 1;
+    }
+
+    /// Follow the pipe under the position. False if there is no pipe to follow.
+    fn step(&mut self, grid: &Grid) -> bool {
+        match grid.get(self.x, self.y) {
+            'L' => {
+                if self.x < self.p_x {
+                    self.m_u();
+                } else {
+                    self.m_r();
+                }
+            }
+
+            '-' => {
+                if self.p_x > self.x {
+                    self.m_l();
+                } else {
+                    self.m_r();
+                }
+            }
+            '|' => {
+                if self.y < self.p_y {
+                    self.m_u();
+                } else {
+                    self.m_d();
+                }
+            }
+
+            'J' => {
+                if self.y > self.p_y {
+                    self.m_l();
+                } else {
+                    self.m_u();
+                }
+            }
+
+            'F' => {
+                if self.y < self.p_y {
+                    self.m_r();
+                } else {
+                    self.m_d();
+                }
+            }
+
+            '7' => {
+                if self.x > self.p_x {
+                    self.m_d();
+                } else {
+                    self.m_l();
+                }
+            }
+
+            _ => return false,
+        }
+        true
+    }
+
+    fn _print(&self, grid: &Grid) {
+        println!("\n");
+        for (y, row) in grid.grid.iter().enumerate() {
+            for (x, c) in row.iter().enumerate() {
+                print!(
+                    "{}",
+                    if (x == self.x) && (y == self.y) {
+                        '*'
+                    } else {
+                        *c
+                    }
+                );
+            }
+            println!();
+        }
+    }
+}
+
+pub struct Grid {
+    grid: Vec<Vec<char>>,
+    /// Positions of the loop tiles in walking order, starting from S.
+    tiles: Vec<(usize, usize)>,
+}
+
+impl Grid {
+    fn from_v_str(input: &Lines) -> Result<Grid, ParseError> {
+        let mut grid: Vec<Vec<char>> = Vec::new();
+        let mut start_count: usize = 0;
+
+        for (i, line) in input.iter().enumerate() {
+            let mut row: Vec<char> = Vec::new();
+            for (j, c) in line.char_indices() {
+                if !"|-LJ7F.S".contains(c) {
+                    let text = &line[j..j + c.len_utf8()];
+                    return Err(input.error(i, text, format!("invalid tile '{}'", c)));
+                }
+                if c == 'S' {
+                    start_count += 1;
+                }
+                row.push(c);
+            }
+
+            if let Some(first) = grid.first() {
+                if row.len() != first.len() {
+                    let message = format!("row is {} wide, expected {}", row.len(), first.len());
+                    return Err(input.error(i, line, message));
+                }
+            }
+            grid.push(row);
+        }
+
+        if start_count != 1 {
+            return Err(input.missing(format!("expected one start tile 'S', found {}", start_count)));
+        }
+
+        let mut grid = Grid { grid, tiles: Vec::new() };
+        grid.tiles = match grid.get_loop() {
+            Some(tiles) => tiles,
+            None => {
+                let start = grid.get_start();
+                let line: &str = &input[start.y];
+                let (j, _) = line.char_indices().nth(start.x).unwrap();
+                return Err(input.error(start.y, &line[j..j + 1], "the pipes from 'S' do not form a loop"));
+            }
+        };
+        Ok(grid)
+    }
+
+    /// Tile at x, y, or '.' outside the grid.
+    fn get(&self, x: usize, y: usize) -> char {
+        match self.grid.get(y).and_then(|row| row.get(x)) {
+            Some(c) => *c,
+            None => '.',
+        }
+    }
+
+    fn get_start(&self) -> Pos {
+        for y in 0..self.grid.len() {
+            for x in 0..self.grid[y].len() {
+                if self.get(x, y) == 'S' {
+                    return Pos::new(x, y);
+                }
+            }
+        }
+
+        Pos::new(0, 0)
+    }
+
+    /// Positions of the loop tiles in walking order, starting from S.
+    /// The walk leaves S towards the first neighbour, clockwise from the right, whose
+    /// pipe connects back to it. None if the pipes from S do not lead back to it.
+    fn get_loop(&self) -> Option<Vec<(usize, usize)>> {
+        let mut pos: Pos = self.get_start();
+        let mut tiles: Vec<(usize, usize)> = vec![(pos.x, pos.y)];
+        //pos._print(&self);
+
+        if "-J7".contains(self.get(pos.x + 1, pos.y)) {
+            pos.m_r();
+        } else if "|LJ".contains(self.get(pos.x, pos.y + 1)) {
+            pos.m_d();
+        } else if "-LF".contains(self.get(pos.x.wrapping_sub(1), pos.y)) {
+            pos.m_l();
+        } else if "|7F".contains(self.get(pos.x, pos.y.wrapping_sub(1))) {
+            pos.m_u();
+        } else {
+            return None;
+        }
+
+        let size = self.grid.len() * self.grid[0].len();
+        while self.get(pos.x, pos.y) != 'S' {
+            tiles.push((pos.x, pos.y));
+            // A walk longer than the grid is going round in a loop without S.
+            if !pos.step(self) || tiles.len() > size {
+                return None;
+            }
+            //pos._print(&self);
+            //thread::sleep(time::Duration::from_millis(1));
+        }
+        Some(tiles)
+    }
+}
+
+/// Count of tiles enclosed by a loop.
+/// Shoelace formula gives the area of the loop, Pick's theorem the tiles inside it.
+fn enclosed_tiles(tiles: &[(usize, usize)]) -> usize {
+    let mut area2: i64 = 0;
+
+    for (i, a) in tiles.iter().enumerate() {
+        let b = tiles[(i + 1) % tiles.len()];
+        area2 += a.0 as i64 * b.1 as i64 - b.0 as i64 * a.1 as i64;
+    }
+
+    ((area2.abs() + 2 - tiles.len() as i64) / 2) as usize
+}
+
+pub struct Day10;
+
+impl Solution for Day10 {
+    const DAY: u8 = 10;
+    /// One row of pipes per line.
+    const NORMALIZE: Normalize = Normalize::TRIMMED;
+
+    type Input = Grid;
+
+    fn parse(input: &Lines) -> Result<Grid, ParseError> {
+        Grid::from_v_str(input)
+    }
+
+    /// Steps along the loop to the point farthest from the start.
+    fn part1(grid: &Grid) -> Answer {
+        (grid.tiles.len() / 2).into()
+    }
+
+    /// Tiles enclosed by the loop.
+    fn part2(grid: &Grid) -> Answer {
+        enclosed_tiles(&grid.tiles).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse_example;
+
+    const EXAMPLE1: &str = include_str!("../fixtures/example1.txt");
+    const EXAMPLE2: &str = include_str!("../fixtures/example2.txt");
+    const EXAMPLE3: &str = include_str!("../fixtures/example3.txt");
+    const EXAMPLE4: &str = include_str!("../fixtures/example4.txt");
+
+    #[test]
+    fn part1_examples() {
+        assert_eq!(Day10::part1(&parse_example::<Day10>(EXAMPLE1)), Answer::Number(4));
+        assert_eq!(Day10::part1(&parse_example::<Day10>(EXAMPLE2)), Answer::Number(8));
+    }
+
+    #[test]
+    fn part2_examples() {
+        assert_eq!(Day10::part2(&parse_example::<Day10>(EXAMPLE3)), Answer::Number(4));
+        assert_eq!(Day10::part2(&parse_example::<Day10>(EXAMPLE4)), Answer::Number(8));
+    }
+
+    #[test]
+    fn step_follows_the_pipes() {
+        let grid = parse_example::<Day10>(EXAMPLE1);
+        // Leaving S at (1, 1) to the right.
+        let mut pos = Pos {
+            x: 2,
+            y: 1,
+            p_x: 1,
+            p_y: 1,
+        };
+
+        let mut visited: Vec<(usize, usize)> = Vec::new();
+        for _ in 0..6 {
+            assert!(pos.step(&grid));
+            visited.push((pos.x, pos.y));
+        }
+        assert_eq!(visited, vec![(3, 1), (3, 2), (3, 3), (2, 3), (1, 3), (1, 2)]);
+    }
+
+    #[test]
+    fn start_in_any_corner_of_the_loop() {
+        let grid = parse_example::<Day10>(".....\n.F-7.\n.|.|.\n.L-S.\n.....");
+        assert_eq!(grid.tiles[..3], [(3, 3), (2, 3), (1, 3)]);
+        assert_eq!(Day10::part1(&grid), Answer::Number(4));
+        assert_eq!(Day10::part2(&grid), Answer::Number(1));
+
+        let grid = parse_example::<Day10>("F-7\nS.|\nL-J");
+        assert_eq!(Day10::part1(&grid), Answer::Number(4));
+    }
+
+    #[test]
+    fn loops_enclosing_nothing() {
+        assert_eq!(Day10::part2(&parse_example::<Day10>("S7\nLJ")), Answer::Number(0));
+        assert_eq!(Day10::part2(&parse_example::<Day10>("S-7\nL-J")), Answer::Number(0));
+    }
+
+    #[test]
+    fn broken_loop_is_an_error() {
+        let lines = Lines::new("bad", ".....\n.F-7.\n.|.|.\n.L.S.\n.....", Day10::NORMALIZE);
+        let err = Day10::parse(&lines).err().unwrap();
+        assert_eq!((err.line, err.column), (4, 4));
+        let lines = Lines::new("bad", "S-", Day10::NORMALIZE);
+        assert!(Day10::parse(&lines).is_err());
+    }
+
+    #[test]
+    fn missing_start_is_an_error() {
+        let lines = Lines::new("bad", "F7\nLJ", Day10::NORMALIZE);
+        assert!(Day10::parse(&lines).is_err());
+    }
+}
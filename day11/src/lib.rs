@@ -0,0 +1,245 @@
+//! Day 11: Cosmic Expansion
+
+use aoc_common::{Answer, Lines, Normalize, ParseError, Solution};
+use std::collections::HashMap;
+
+fn check_row_empty(input: &str) -> bool {
+    !input.contains('#')
+}
+
+fn check_column_empty(input: &[String], column: usize) -> bool {
+    for s in input.iter() {
+        if s.chars().nth(column).unwrap() == '#' {
+            return false;
+        }
+    }
+    true
+}
+
+fn expand_space(input: &mut Vec<String>) {
+    let mut expanding_rows: Vec<usize> = Vec::new();
+    let mut expanding_columns: Vec<usize> = Vec::new();
+
+    for (row, line) in input.iter().enumerate() {
+        if check_row_empty(line) {
+            expanding_rows.push(row);
+        }
+    }
+    for column in 0..input[0].len() {
+        if check_column_empty(input, column) {
+            expanding_columns.push(column);
+        }
+    }
+    for (i, column) in expanding_columns.iter().enumerate() {
+        //eprintln!("column {} expands", column);
+
+        for row in input.iter_mut() {
+            *row = row[0..*column + i].to_string() + "." + &row[*column + i..row.len()];
+        }
+    }
+
+    for (i, row) in expanding_rows.iter().enumerate() {
+        //eprintln!("Row {} expands", row);
+        let new_row = [std::iter::repeat_n(".", input[0].len())
+            .collect::<String>()];
+        input.splice(*row + i..*row + i, new_row.iter().cloned());
+    }
+}
+
+fn get_galaxy_positions(input: &[String]) -> Vec<(usize, usize)> {
+    let mut res: Vec<(usize, usize)> = Vec::new();
+
+    for y in 0..input.len() {
+        for x in 0..input[0].len() {
+            if input[y].chars().nth(x).unwrap() == '#' {
+                res.push((x, y));
+            }
+        }
+    }
+    res
+}
+
+/// Galaxy positions when every empty row and column is replaced by `factor` of them.
+fn get_expanded_positions(input: &[String], factor: usize) -> Vec<(usize, usize)> {
+    let empty_rows: Vec<usize> = (0..input.len())
+        .filter(|row| check_row_empty(&input[*row]))
+        .collect();
+    let empty_columns: Vec<usize> = (0..input[0].len())
+        .filter(|column| check_column_empty(input, *column))
+        .collect();
+
+    get_galaxy_positions(input)
+        .iter()
+        .map(|(x, y)| {
+            let columns_before = empty_columns.iter().filter(|c| *c < x).count();
+            let rows_before = empty_rows.iter().filter(|r| *r < y).count();
+            (
+                x + columns_before * (factor - 1),
+                y + rows_before * (factor - 1),
+            )
+        })
+        .collect()
+}
+
+fn _print_space(space: &[String]) {
+    for row in space.iter() {
+        println!("{}", row);
+    }
+    //println!("");
+}
+
+fn _replace_with_num(grid: &mut [String], galaxy_positions: &[(usize, usize)]) {
+    for (i, position) in galaxy_positions.iter().enumerate() {
+        grid[position.1].replace_range(position.0..position.0 + 1, (1 + i).to_string().as_str());
+    }
+}
+
+fn get_distance(a: &(usize, usize), b: &(usize, usize), _i: usize, _j: usize) -> usize {
+    a.0.abs_diff(b.0) + a.1.abs_diff(b.1)
+}
+
+fn make_pos_key(a: &(usize, usize), b: &(usize, usize)) -> String {
+    if a.0 < b.0 || (a.0 == b.0 && a.1 < b.1) {
+        std::format!("{},{}-{},{}", a.0, b.0, a.1, b.1)
+    } else {
+        std::format!("{},{}-{},{}", b.0, a.0, b.1, a.1)
+    }
+}
+
+/*
+124,10 -> 53,19
+
+-> 53,124-19,10
+
+x0,y0,x1,y1
+
+x0 < x1:
+x0,x1-y0,y1
+x0 > x1:
+x1,x0-y1,y0
+
+0,10 -> 5,10 => 0,5-10,10
+3,10 -> 3,2 => 3,3-10,2
+3,2 -> 3,10 => 3,3-2,10
+
+*/
+/// Sum of the shortest paths between every pair of galaxies.
+fn get_distance_sum(galaxy_positions: &[(usize, usize)]) -> usize {
+    let mut distance_sum = 0;
+    let mut star = 0;
+    let mut galaxymap: HashMap<String, usize> = HashMap::new();
+
+    for a in galaxy_positions.iter() {
+        star += 1;
+        let mut star_b = 0;
+        for b in galaxy_positions.iter() {
+            let key = make_pos_key(a, b);
+            if a.0 != b.0 || a.1 != b.1 {
+                galaxymap.insert(key, get_distance(a, b, star, star_b));
+                star_b += 1;
+            }
+        }
+        //println!("Checked len: {}", );
+    }
+
+    galaxymap.values().for_each(|x| distance_sum += x);
+    distance_sum
+}
+
+pub struct Day11;
+
+impl Solution for Day11 {
+    const DAY: u8 = 11;
+    /// One row of space per line.
+    const NORMALIZE: Normalize = Normalize::TRIMMED;
+
+    type Input = Vec<String>;
+
+    fn parse(input: &Lines) -> Result<Vec<String>, ParseError> {
+        if input.is_empty() {
+            return Err(input.missing("expected at least one row of space"));
+        }
+        for (i, line) in input.iter().enumerate() {
+            if let Some(j) = line.find(|c: char| c != '.' && c != '#') {
+                let c: char = line[j..].chars().next().unwrap();
+                let text = &line[j..j + c.len_utf8()];
+                return Err(input.error(i, text, format!("expected '.' or '#', found '{}'", c)));
+            }
+            if line.len() != input[0].len() {
+                let message = format!("row is {} wide, expected {}", line.len(), input[0].len());
+                return Err(input.error(i, line, message));
+            }
+        }
+        Ok(input.to_vec())
+    }
+
+    /// Distances when empty rows and columns are twice as big.
+    fn part1(input: &Vec<String>) -> Answer {
+        let mut input: Vec<String> = input.clone();
+        expand_space(&mut input);
+        let galaxy_positions: Vec<(usize, usize)> = get_galaxy_positions(&input);
+
+        get_distance_sum(&galaxy_positions).into()
+    }
+
+    /// Distances when empty rows and columns are a million times bigger.
+    fn part2(input: &Vec<String>) -> Answer {
+        get_distance_sum(&get_expanded_positions(input, 1_000_000)).into()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::parse_example;
+
+    const EXAMPLE: &str = include_str!("../fixtures/example.txt");
+
+    #[test]
+    fn part1_example() {
+        let input = parse_example::<Day11>(EXAMPLE);
+        assert_eq!(Day11::part1(&input), Answer::Number(374));
+    }
+
+    #[test]
+    fn part2_example() {
+        let input = parse_example::<Day11>(EXAMPLE);
+        assert_eq!(Day11::part2(&input), Answer::Number(82000210));
+    }
+
+    #[test]
+    fn expand_space_doubles_empty_rows_and_columns() {
+        let mut space = parse_example::<Day11>(EXAMPLE);
+        expand_space(&mut space);
+
+        let expected = "\
+....#........
+.........#...
+#............
+.............
+.............
+........#....
+.#...........
+............#
+.............
+.............
+.........#...
+#....#.......";
+        assert_eq!(space.join("\n"), expected);
+    }
+
+    #[test]
+    fn empty_input_is_an_error() {
+        let lines = Lines::new("empty", "\n\n", Day11::NORMALIZE);
+        let err = Day11::parse(&lines).err().unwrap();
+        assert_eq!(err.line, 3);
+    }
+
+    #[test]
+    fn expansion_factors_of_example() {
+        let space = parse_example::<Day11>(EXAMPLE);
+        assert_eq!(get_distance_sum(&get_expanded_positions(&space, 2)), 374);
+        assert_eq!(get_distance_sum(&get_expanded_positions(&space, 10)), 1030);
+        assert_eq!(get_distance_sum(&get_expanded_positions(&space, 100)), 8410);
+    }
+}